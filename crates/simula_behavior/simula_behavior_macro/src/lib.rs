@@ -72,6 +72,17 @@ fn impl_behavior_factory(ast: &syn::DeriveInput) -> TokenStream {
             })
             .collect();
 
+        let scripts_variant_impls: Vec<_> = data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                quote! {
+                    Self::#variant_ident(data) => BehaviorSpec::scripts(data),
+                }
+            })
+            .collect();
+
         let reflect_variant_impls: Vec<_> = data_enum
             .variants
             .iter()
@@ -117,6 +128,40 @@ fn impl_behavior_factory(ast: &syn::DeriveInput) -> TokenStream {
             })
             .collect();
 
+        let compare_from_variant_impls: Vec<_> = data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_argument = get_variant_argument(&variant.fields).unwrap();
+                quote! {
+                    Self::#variant_ident(data) => Reflect::reflect_partial_eq(data, world.get::<#variant_argument>(entity).ok_or(BehaviorMissing)?),
+                }
+            })
+            .collect();
+
+        let save_state_variant_impls: Vec<_> = data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                quote! {
+                    Self::#variant_ident(data) => BehaviorSpec::save_state(data, clock),
+                }
+            })
+            .collect();
+
+        let restore_state_variant_impls: Vec<_> = data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                quote! {
+                    Self::#variant_ident(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+                }
+            })
+            .collect();
+
         let list_variant_impls: Vec<_> = data_enum
             .variants
             .iter()
@@ -162,6 +207,12 @@ fn impl_behavior_factory(ast: &syn::DeriveInput) -> TokenStream {
                     }
                 }
 
+                fn scripts(&self) -> Vec<&str> {
+                    match self {
+                        #(#scripts_variant_impls)*
+                    }
+                }
+
                 fn inner_reflect(&self) -> &dyn Reflect {
                     match self {
                         #(#reflect_variant_impls)*
@@ -203,6 +254,29 @@ fn impl_behavior_factory(ast: &syn::DeriveInput) -> TokenStream {
                     Ok(())
                 }
 
+                fn compare_from(&self, entity: Entity, world: &World) -> Result<Option<bool>, BehaviorMissing> {
+                    Ok(match self {
+                        #(#compare_from_variant_impls)*
+                    })
+                }
+
+                fn save_state(&self, clock: &BehaviorClock) -> Option<BehaviorNodeState> {
+                    match self {
+                        #(#save_state_variant_impls)*
+                    }
+                }
+
+                fn restore_state(
+                    &mut self,
+                    state: &BehaviorNodeState,
+                    clock: &BehaviorClock,
+                    entity_map: &bevy::ecs::entity::EntityMap,
+                ) {
+                    match self {
+                        #(#restore_state_variant_impls)*
+                    }
+                }
+
                 fn list() -> Vec<Self> {
                     vec![
                         #(#list_variant_impls)*
@@ -217,6 +291,336 @@ fn impl_behavior_factory(ast: &syn::DeriveInput) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(BehaviorSpec, attributes(behavior))]
+pub fn behavior_spec_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_behavior_spec(&ast).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+fn impl_behavior_spec(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let mut typ = None;
+    let mut label = name.to_string();
+    let mut icon = String::new();
+    let mut desc = None;
+    for meta in get_behavior_metas(&ast.attrs)? {
+        match &meta {
+            syn::Meta::Path(path) if path.is_ident("action") => typ = Some(quote!(Action)),
+            syn::Meta::Path(path) if path.is_ident("composite") => typ = Some(quote!(Composite)),
+            syn::Meta::Path(path) if path.is_ident("decorator") => typ = Some(quote!(Decorator)),
+            syn::Meta::Path(path) if path.is_ident("subtree") => typ = Some(quote!(Subtree)),
+            syn::Meta::NameValue(name_value) if name_value.path.is_ident("name") => {
+                label = get_lit_str(name_value)?;
+            }
+            syn::Meta::NameValue(name_value) if name_value.path.is_ident("icon") => {
+                icon = get_lit_str(name_value)?;
+            }
+            syn::Meta::NameValue(name_value) if name_value.path.is_ident("desc") => {
+                desc = Some(get_lit_str(name_value)?);
+            }
+            _ => return Err(syn::Error::new_spanned(meta, "Unknown behavior attribute")),
+        }
+    }
+    let typ = typ.ok_or_else(|| {
+        syn::Error::new_spanned(
+            name,
+            "Expected #[behavior(action)], #[behavior(composite)], #[behavior(decorator)] \
+            or #[behavior(subtree)] attribute",
+        )
+    })?;
+
+    // Description defaults to the doc comment of the struct
+    let desc = match desc {
+        Some(desc) => desc,
+        None => get_doc(&ast.attrs)?,
+    };
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let scripts_fn = impl_scripts(ast)?;
+    let save_fns = impl_save_state(ast)?;
+    let gen = quote! {
+        impl #impl_generics BehaviorSpec for #name #ty_generics #where_clause {
+            const TYPE: BehaviorType = BehaviorType::#typ;
+            const NAME: &'static str = #label;
+            const ICON: &'static str = #icon;
+            const DESC: &'static str = #desc;
+
+            #scripts_fn
+            #save_fns
+        }
+    };
+    Ok(gen.into())
+}
+
+// scripts of fields marked #[behavior(prop)], in field order
+fn impl_scripts(ast: &syn::DeriveInput) -> syn::Result<impl quote::ToTokens> {
+    let syn::Data::Struct(data_struct) = &ast.data else {
+        return Ok(quote!());
+    };
+
+    let mut field_idents = vec![];
+    for field in data_struct.fields.iter() {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+        for meta in get_behavior_metas(&field.attrs)? {
+            if matches!(&meta, syn::Meta::Path(path) if path.is_ident("prop")) {
+                field_idents.push(field_ident);
+            }
+        }
+    }
+    if field_idents.is_empty() {
+        return Ok(quote!());
+    }
+
+    Ok(quote! {
+        fn scripts(&self) -> Vec<&str> {
+            [#(self.#field_idents.script(),)*].into_iter().flatten().collect()
+        }
+    })
+}
+
+// save_state and restore_state of fields marked #[behavior(save)] or
+// #[behavior(save_time)], saved as a tuple in field order
+fn impl_save_state(ast: &syn::DeriveInput) -> syn::Result<impl quote::ToTokens> {
+    let syn::Data::Struct(data_struct) = &ast.data else {
+        return Ok(quote!());
+    };
+
+    let mut field_idents = vec![];
+    let mut saved_idents = vec![];
+    let mut saved_types = vec![];
+    let mut save_values = vec![];
+    let mut restore_values = vec![];
+    let mut has_time = false;
+    let mut has_field = false;
+    for field in data_struct.fields.iter() {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+        let field_ty = &field.ty;
+        // Saved values are bound apart from fields, so fields can be named like
+        // the restore_state arguments
+        let saved_ident = quote::format_ident!("saved_{}", field_ident);
+        for meta in get_behavior_metas(&field.attrs)? {
+            match &meta {
+                syn::Meta::Path(path) if path.is_ident("save") => {
+                    has_field = true;
+                    saved_types.push(quote!(<#field_ty as BehaviorSaveField>::Saved));
+                    save_values.push(quote!(BehaviorSaveField::save_field(&self.#field_ident)));
+                    restore_values.push(quote! {
+                        <#field_ty as BehaviorSaveField>::restore_field(#saved_ident, entity_map)
+                    });
+                }
+                syn::Meta::Path(path) if path.is_ident("save_time") => {
+                    has_time = true;
+                    saved_types.push(quote!(#field_ty));
+                    save_values
+                        .push(quote!(BehaviorSaveTime::save_time(&self.#field_ident, clock)));
+                    restore_values
+                        .push(quote!(BehaviorSaveTime::restore_time(#saved_ident, clock)));
+                }
+                _ => continue,
+            }
+            field_idents.push(field_ident);
+            saved_idents.push(saved_ident.clone());
+        }
+    }
+    if field_idents.is_empty() {
+        return Ok(quote!());
+    }
+
+    let clock = if has_time {
+        quote!(clock)
+    } else {
+        quote!(_clock)
+    };
+    let entity_map = if has_field {
+        quote!(entity_map)
+    } else {
+        quote!(_entity_map)
+    };
+    Ok(quote! {
+        fn save_state(&self, #clock: &BehaviorClock) -> Option<BehaviorNodeState> {
+            BehaviorNodeState::new(&(#(#save_values,)*))
+        }
+
+        fn restore_state(
+            &mut self,
+            state: &BehaviorNodeState,
+            #clock: &BehaviorClock,
+            #entity_map: &bevy::ecs::entity::EntityMap,
+        ) {
+            if let Some((#(#saved_idents,)*)) = state.get::<(#(#saved_types,)*)>() {
+                #(self.#field_idents = #restore_values;)*
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(BehaviorUI, attributes(behavior))]
+pub fn behavior_ui_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_behavior_ui(&ast).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+fn impl_behavior_ui(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let syn::Data::Struct(data_struct) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "BehaviorUI can only be derived for structs",
+        ));
+    };
+
+    // Behavior props and properties are edited, readonly fields are only shown
+    // while the behavior runs
+    let mut ui_fields = vec![];
+    let mut ui_readonly_fields = vec![];
+    let mut running_fields = vec![];
+    for field in data_struct.fields.iter() {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+        let field_label = field_ident.to_string();
+        for meta in get_behavior_metas(&field.attrs)? {
+            match &meta {
+                syn::Meta::Path(path) if path.is_ident("prop") => {
+                    ui_fields.push(quote! {
+                        changed |= self.#field_ident.ui(Some(#field_label), state, ui, type_registry);
+                    });
+                    ui_readonly_fields.push(quote! {
+                        self.#field_ident.ui_readonly(Some(#field_label), state, ui, type_registry);
+                    });
+                }
+                syn::Meta::Path(path) if path.is_ident("property") => {
+                    ui_fields.push(quote! {
+                        changed |= Self::add_property(self.#field_ident.as_reflect_mut(), #field_label, state, ui, type_registry);
+                    });
+                    ui_readonly_fields.push(quote! {
+                        Self::add_property_readonly(self.#field_ident.as_reflect(), #field_label, state, ui, type_registry);
+                    });
+                }
+                syn::Meta::Path(path) if path.is_ident("readonly") => {
+                    running_fields.push(quote! {
+                        Self::add_property_readonly(self.#field_ident.as_reflect(), #field_label, state, ui, type_registry);
+                    });
+                }
+                // Saved by the BehaviorSpec derive
+                syn::Meta::Path(path) if path.is_ident("save") || path.is_ident("save_time") => {}
+                _ => return Err(syn::Error::new_spanned(meta, "Unknown behavior attribute")),
+            }
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    if ui_fields.is_empty() && running_fields.is_empty() {
+        return Ok(quote! {
+            impl #impl_generics BehaviorUI for #name #ty_generics #where_clause {}
+        }
+        .into());
+    }
+    let ui_fn = if ui_fields.is_empty() {
+        quote! {
+            fn ui(
+                &mut self,
+                _label: Option<&str>,
+                _state: Option<protocol::BehaviorState>,
+                _ui: &mut bevy_inspector_egui::egui::Ui,
+                _type_registry: &bevy::reflect::TypeRegistry,
+            ) -> bool {
+                false
+            }
+        }
+    } else {
+        quote! {
+            fn ui(
+                &mut self,
+                _label: Option<&str>,
+                state: Option<protocol::BehaviorState>,
+                ui: &mut bevy_inspector_egui::egui::Ui,
+                type_registry: &bevy::reflect::TypeRegistry,
+            ) -> bool {
+                let mut changed = false;
+                #(#ui_fields)*
+                changed
+            }
+        }
+    };
+    let running_body = if running_fields.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            if state.is_some() {
+                #(#running_fields)*
+            }
+        }
+    };
+    let gen = quote! {
+        impl #impl_generics BehaviorUI for #name #ty_generics #where_clause {
+            #ui_fn
+
+            fn ui_readonly(
+                &self,
+                _label: Option<&str>,
+                state: Option<protocol::BehaviorState>,
+                ui: &mut bevy_inspector_egui::egui::Ui,
+                type_registry: &bevy::reflect::TypeRegistry,
+            ) {
+                #(#ui_readonly_fields)*
+                #running_body
+            }
+        }
+    };
+    Ok(gen.into())
+}
+
+// All metas in #[behavior(...)] attributes
+fn get_behavior_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut metas = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("behavior") {
+            continue;
+        }
+        let syn::Meta::List(list) = attr.parse_meta()? else {
+            return Err(syn::Error::new_spanned(attr, "Expected #[behavior(...)]"));
+        };
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(meta) => metas.push(meta),
+                syn::NestedMeta::Lit(lit) => {
+                    return Err(syn::Error::new_spanned(lit, "Unknown behavior attribute"));
+                }
+            }
+        }
+    }
+    Ok(metas)
+}
+
+fn get_lit_str(name_value: &syn::MetaNameValue) -> syn::Result<String> {
+    match &name_value.lit {
+        syn::Lit::Str(lit_str) => Ok(lit_str.value()),
+        lit => Err(syn::Error::new_spanned(lit, "Expected a string")),
+    }
+}
+
+// Lines of a doc comment, joined with spaces
+fn get_doc(attrs: &[syn::Attribute]) -> syn::Result<String> {
+    let mut lines = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        if let syn::Meta::NameValue(name_value) = attr.parse_meta()? {
+            let line = get_lit_str(&name_value)?;
+            if !line.trim().is_empty() {
+                lines.push(line.trim().to_string());
+            }
+        }
+    }
+    Ok(lines.join(" "))
+}
+
 fn get_attributes_type(attrs: &[syn::Attribute]) -> syn::Result<syn::Type> {
     for attr in attrs {
         if attr.path.is_ident("BehaviorAttributes") {
@@ -0,0 +1,128 @@
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, AsyncComputeTaskPool, Task},
+    utils::BoxedFuture,
+};
+
+/// An action whose work is a future, like a planner, a pathfinder or a database
+/// query. The future is spawned on the `AsyncComputeTaskPool` when the node
+/// starts, the node keeps running while it is pending, and completes with
+/// success or failure from its result. Add `async_action::run::<A>` to the
+/// behavior schedule for each action type.
+pub trait AsyncAction: BehaviorSpec {
+    /// Create the work of the action, when its node starts. Props are fetched
+    /// with `node` and `scripts`, like in node systems. Returns `None` while
+    /// props are not ready, it is called again on the next tick. An error
+    /// fails the node.
+    fn work(
+        &mut self,
+        node: &BehaviorNode,
+        scripts: &mut ScriptQueries,
+    ) -> Option<Result<BoxedFuture<'static, Result<(), String>>, String>>;
+}
+
+/// Work of a running behavior node, the node completes with its result. The
+/// work is cancelled if the node is stopped, or if this component is removed.
+#[derive(Component)]
+pub struct BehaviorTask {
+    task: Option<Task<Result<(), String>>>,
+    result: Option<Result<(), String>>,
+}
+
+impl BehaviorTask {
+    /// Spawn work on the `AsyncComputeTaskPool`
+    pub fn spawn(work: BoxedFuture<'static, Result<(), String>>) -> Self {
+        Self {
+            task: Some(AsyncComputeTaskPool::get().spawn(work)),
+            result: None,
+        }
+    }
+
+    /// Block the thread until the work is done, the node completes with its
+    /// result on its next tick. Like to finish work before saving, or in tests.
+    pub fn block_on(&mut self) {
+        if let Some(task) = self.task.take() {
+            self.result = Some(future::block_on(task));
+        }
+    }
+
+    // Result of the work, if done
+    fn poll(&mut self) -> Option<Result<(), String>> {
+        if let Some(result) = self.result.take() {
+            return Some(result);
+        }
+        let task = self.task.as_mut()?;
+        future::block_on(future::poll_once(task))
+    }
+}
+
+/// Spawn the work of async actions when their node starts. Running nodes
+/// without work, like nodes restored from a save or a hot reload, spawn it
+/// again, as tasks are not saved.
+pub fn run<A: AsyncAction>(
+    mut commands: Commands,
+    mut actions: Query<
+        (
+            Entity,
+            &mut A,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+            Option<&BehaviorTask>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut action, node, started, task) in &mut actions {
+        if started.is_none() && task.is_some() {
+            continue;
+        }
+        match action.work(node, &mut scripts) {
+            Some(Ok(work)) => {
+                commands.entity(entity).insert(BehaviorTask::spawn(work));
+            }
+            Some(Err(err)) => {
+                error!("Action failed: {}", err);
+                commands.entity(entity).remove::<BehaviorTask>();
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            None => {
+                // Props are not ready, work of a previous start is stale
+                commands.entity(entity).remove::<BehaviorTask>();
+            }
+        }
+    }
+}
+
+/// Complete running nodes once their work is done
+pub fn poll_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut BehaviorTask), BehaviorRunQuery>,
+) {
+    for (entity, mut task) in &mut tasks {
+        let Some(result) = task.poll() else {
+            continue;
+        };
+        commands.entity(entity).remove::<BehaviorTask>();
+        match result {
+            Ok(()) => {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            Err(err) => {
+                error!("Task failed: {}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
+
+/// Cancel the work of stopped nodes, dropping a task cancels it
+pub fn cancel_tasks(
+    mut commands: Commands,
+    tasks: Query<Entity, (With<BehaviorTask>, With<BehaviorStopped>)>,
+) {
+    for entity in &tasks {
+        commands.entity(entity).remove::<BehaviorTask>();
+    }
+}
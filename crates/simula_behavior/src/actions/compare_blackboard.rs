@@ -0,0 +1,116 @@
+use crate::{blackboard, prelude::*};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use std::cmp::Ordering;
+
+/// How to compare a blackboard key with a value
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum CompareOp {
+    #[default]
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl CompareOp {
+    pub fn test(&self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Equal => ordering == Ordering::Equal,
+            CompareOp::NotEqual => ordering != Ordering::Equal,
+            CompareOp::Less => ordering == Ordering::Less,
+            CompareOp::LessOrEqual => ordering != Ordering::Greater,
+            CompareOp::Greater => ordering == Ordering::Greater,
+            CompareOp::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// Compare a blackboard key with a value. Succeeds if the comparison holds,
+/// fails if it doesn't, if the key is missing or the types can't be compared.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, name = "Compare Blackboard", icon = "⚖")]
+pub struct CompareBlackboard {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    #[behavior(property)]
+    pub op: CompareOp,
+    #[serde(default)]
+    #[behavior(prop)]
+    pub value: BehaviorPropGeneric<BlackboardValue>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut compare_blackboards: Query<
+        (
+            Entity,
+            &mut CompareBlackboard,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut compare_blackboard, node, started) in &mut compare_blackboards {
+        // Eval props again every time we start
+        if started.is_some() {
+            compare_blackboard.key.value = BehaviorPropValue::None;
+            compare_blackboard.value.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = compare_blackboard.key.value {
+            let result = compare_blackboard.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = compare_blackboard.value.value {
+            let result = compare_blackboard.value.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), BehaviorPropValue::Some(value)) = (
+            &compare_blackboard.key.value,
+            &compare_blackboard.value.value,
+        ) {
+            let ordering = scripts
+                .script_ctx_mut(node.tree)
+                .ok()
+                .and_then(|script_ctx| blackboard::get_value(script_ctx, key))
+                .and_then(|current| current.compare(value));
+            match ordering {
+                Some(ordering) if compare_blackboard.op.test(ordering) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                _ => {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
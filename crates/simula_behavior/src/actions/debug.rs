@@ -1,71 +1,48 @@
 use crate::prelude::*;
-use crate::property_ui_readonly;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 #[derive(
-    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    InspectorOptions,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    action,
+    icon = "👁",
+    desc = "Display a debug message and complete with success or failure"
 )]
 #[reflect(InspectorOptions)]
 pub struct Debug {
     #[serde(default)]
+    #[behavior(prop)]
     pub message: BehaviorPropStr,
     #[serde(default)]
+    #[behavior(prop)]
     pub fail: BehaviorPropGeneric<bool>,
     #[serde(default)]
+    #[behavior(prop)]
     pub duration: BehaviorPropGeneric<f64>,
     #[serde(skip)]
+    #[behavior(readonly, save_time)]
     pub start: f64,
     #[serde(skip)]
+    #[behavior(readonly, save)]
     pub ticks: u64,
 }
 
-impl BehaviorSpec for Debug {
-    const TYPE: BehaviorType = BehaviorType::Action;
-    const NAME: &'static str = "Debug";
-    const ICON: &'static str = "👁";
-    const DESC: &'static str = "Display a debug message and complete with success or failure";
-}
-
-impl BehaviorUI for Debug {
-    fn ui(
-        &mut self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) -> bool {
-        let mut changed = false;
-        changed |= behavior_ui!(self, message, state, ui, type_registry);
-        changed |= behavior_ui!(self, fail, state, ui, type_registry);
-        changed |= behavior_ui!(self, duration, state, ui, type_registry);
-        changed
-    }
-
-    fn ui_readonly(
-        &self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) {
-        behavior_ui_readonly!(self, message, state, ui, type_registry);
-        behavior_ui_readonly!(self, fail, state, ui, type_registry);
-        behavior_ui_readonly!(self, duration, state, ui, type_registry);
-
-        match state {
-            Some(_) => {
-                property_ui_readonly!(self, start, state, ui, type_registry);
-                property_ui_readonly!(self, ticks, state, ui, type_registry);
-            }
-            _ => {}
-        }
-    }
-}
-
 pub fn run(
-    time: Res<Time>,
+    clock: Res<BehaviorClock>,
     mut commands: Commands,
     mut debug_actions: Query<
         (
@@ -116,7 +93,7 @@ pub fn run(
             &debug_action.fail.value.clone(),
             &debug_action.duration.value.clone(),
         ) {
-            let elapsed = time.elapsed_seconds_f64();
+            let elapsed = clock.elapsed_seconds_f64();
             debug_action.ticks += 1;
             if started.is_some() {
                 debug_action.start = elapsed;
@@ -0,0 +1,67 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Raise a named signal, for this tree or for all trees, and succeed.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, name = "Emit Signal", icon = "📣")]
+pub struct EmitSignal {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub signal: BehaviorPropStr,
+    #[serde(default)]
+    #[behavior(property)]
+    pub scope: SignalScope,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut emits: Query<
+        (
+            Entity,
+            &mut EmitSignal,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut signals: EventWriter<BehaviorSignal>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut emit, node, started) in &mut emits {
+        // Eval signal name again every time we start
+        if started.is_some() {
+            emit.signal.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = emit.signal.value {
+            let result = emit.signal.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(name) = &emit.signal.value {
+            let signal = match emit.scope {
+                SignalScope::Tree => BehaviorSignal::tree(node.tree, name.clone()),
+                SignalScope::Global => BehaviorSignal::global(name.clone()),
+            };
+            signals.send(signal);
+            commands.entity(entity).insert(BehaviorSuccess);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{blackboard, prelude::*};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Add an amount to a numeric blackboard key and succeed. A missing key starts
+/// at zero. Fails if the key is not a number.
+#[derive(
+    Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, BehaviorSpec, BehaviorUI,
+)]
+#[behavior(action, name = "Increment Blackboard", icon = "±")]
+pub struct IncrementBlackboard {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub key: BehaviorPropStr,
+    #[serde(default = "IncrementBlackboard::default_amount")]
+    #[behavior(prop)]
+    pub amount: BehaviorPropGeneric<BlackboardValue>,
+}
+
+impl IncrementBlackboard {
+    fn default_amount() -> BehaviorPropGeneric<BlackboardValue> {
+        BehaviorPropGeneric {
+            prop: BehaviorEval::Value(BlackboardValue::Int(1)),
+            ..default()
+        }
+    }
+}
+
+impl Default for IncrementBlackboard {
+    fn default() -> Self {
+        Self {
+            key: default(),
+            amount: Self::default_amount(),
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut increment_blackboards: Query<
+        (
+            Entity,
+            &mut IncrementBlackboard,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    blackboards: Query<&Blackboard>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut increment_blackboard, node, started) in &mut increment_blackboards {
+        // Eval props again every time we start
+        if started.is_some() {
+            increment_blackboard.key.value = BehaviorPropValue::None;
+            increment_blackboard.amount.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = increment_blackboard.key.value {
+            let result = increment_blackboard.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = increment_blackboard.amount.value {
+            let result = increment_blackboard.amount.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), BehaviorPropValue::Some(amount)) = (
+            &increment_blackboard.key.value,
+            &increment_blackboard.amount.value,
+        ) {
+            let schema = blackboards
+                .get(node.tree)
+                .ok()
+                .map(|blackboard| &blackboard.schema);
+            let result = scripts.script_ctx_mut(node.tree).and_then(|script_ctx| {
+                let current =
+                    blackboard::get_value(script_ctx, key).unwrap_or(BlackboardValue::Int(0));
+                let value = current
+                    .add(amount)
+                    .ok_or_else(|| format!("Cannot increment {:?} by {:?}", current, amount))?;
+                blackboard::set_value(script_ctx, schema, key, value)
+            });
+            match result {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Blackboard errored: {}", err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
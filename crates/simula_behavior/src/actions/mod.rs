@@ -1,5 +1,17 @@
+pub mod async_action;
+pub mod compare_blackboard;
 pub mod debug;
+pub mod emit_signal;
+pub mod increment_blackboard;
+pub mod set_blackboard;
 pub mod wait;
+pub mod wait_for_event;
 
+pub use async_action::{AsyncAction, BehaviorTask};
+pub use compare_blackboard::{CompareBlackboard, CompareOp};
 pub use debug::Debug;
+pub use emit_signal::EmitSignal;
+pub use increment_blackboard::IncrementBlackboard;
+pub use set_blackboard::SetBlackboard;
 pub use wait::Wait;
+pub use wait_for_event::WaitForEvent;
@@ -0,0 +1,90 @@
+use crate::{blackboard, prelude::*};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Set a blackboard key to a value and succeed. Fails if the value doesn't
+/// match the type declared in the blackboard schema.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, name = "Set Blackboard", icon = "✎")]
+pub struct SetBlackboard {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    #[behavior(prop)]
+    pub value: BehaviorPropGeneric<BlackboardValue>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut set_blackboards: Query<
+        (
+            Entity,
+            &mut SetBlackboard,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    blackboards: Query<&Blackboard>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut set_blackboard, node, started) in &mut set_blackboards {
+        // Eval props again every time we start
+        if started.is_some() {
+            set_blackboard.key.value = BehaviorPropValue::None;
+            set_blackboard.value.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = set_blackboard.key.value {
+            let result = set_blackboard.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = set_blackboard.value.value {
+            let result = set_blackboard.value.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), BehaviorPropValue::Some(value)) =
+            (&set_blackboard.key.value, &set_blackboard.value.value)
+        {
+            let schema = blackboards
+                .get(node.tree)
+                .ok()
+                .map(|blackboard| &blackboard.schema);
+            let result = scripts.script_ctx_mut(node.tree).and_then(|script_ctx| {
+                blackboard::set_value(script_ctx, schema, key, value.clone())
+            });
+            match result {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Blackboard errored: {}", err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -1,68 +1,46 @@
 use crate::prelude::*;
-use crate::property_ui_readonly;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// A wait will succeed after a specified amount of time.
 #[derive(
-    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    InspectorOptions,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    action,
+    icon = "⌛",
+    desc = "Wait for a specified amount of time and then complete with success or failure."
 )]
 #[reflect(InspectorOptions)]
 pub struct Wait {
     #[serde(default)]
+    #[behavior(prop)]
     pub duration: BehaviorPropGeneric<f64>,
     #[serde(default)]
+    #[behavior(prop)]
     pub fail: BehaviorPropGeneric<bool>,
     #[serde(skip)]
+    #[behavior(readonly, save_time)]
     pub start: f64,
     #[serde(skip)]
+    #[behavior(readonly, save)]
     pub ticks: u64,
 }
 
-impl BehaviorSpec for Wait {
-    const TYPE: BehaviorType = BehaviorType::Action;
-    const NAME: &'static str = "Wait";
-    const ICON: &'static str = "⌛";
-    const DESC: &'static str = "Wait for a specified amount of time and then complete with \
-    success or failure.";
-}
-
-impl BehaviorUI for Wait {
-    fn ui(
-        &mut self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) -> bool {
-        let mut changed = false;
-        changed |= behavior_ui!(self, fail, state, ui, type_registry);
-        changed |= behavior_ui!(self, duration, state, ui, type_registry);
-        changed
-    }
-
-    fn ui_readonly(
-        &self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) {
-        behavior_ui_readonly!(self, fail, state, ui, type_registry);
-        behavior_ui_readonly!(self, duration, state, ui, type_registry);
-        match state {
-            Some(_) => {
-                property_ui_readonly!(self, start, state, ui, type_registry);
-                property_ui_readonly!(self, ticks, state, ui, type_registry);
-            }
-            _ => {}
-        }
-    }
-}
-
 pub fn run(
-    time: Res<Time>,
+    clock: Res<BehaviorClock>,
     mut commands: Commands,
     mut waits: Query<
         (Entity, &mut Wait, &BehaviorNode, Option<&BehaviorStarted>),
@@ -92,7 +70,7 @@ pub fn run(
         if let (BehaviorPropValue::Some(wait_fail), BehaviorPropValue::Some(wait_duration)) =
             (&wait.fail.value.clone(), &wait.duration.value.clone())
         {
-            let elapsed = time.elapsed_seconds_f64();
+            let elapsed = clock.elapsed_seconds_f64();
             wait.ticks += 1;
             if started.is_some() {
                 wait.start = elapsed;
@@ -0,0 +1,63 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Wait for a named signal, raised for this tree or for all trees, and succeed.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, name = "Wait For Event", icon = "👂")]
+pub struct WaitForEvent {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub signal: BehaviorPropStr,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut waits: Query<
+        (
+            Entity,
+            &mut WaitForEvent,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut signals: EventReader<BehaviorSignal>,
+    mut scripts: ScriptQueries,
+) {
+    // Signals are read every frame, even if nobody is waiting for them
+    let signals: Vec<&BehaviorSignal> = signals.iter().collect();
+    for (entity, mut wait, node, started) in &mut waits {
+        // Eval signal name again every time we start
+        if started.is_some() {
+            wait.signal.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = wait.signal.value {
+            let result = wait.signal.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(name) = &wait.signal.value {
+            if signals.iter().any(|signal| signal.is_for(node.tree, name)) {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+        }
+    }
+}
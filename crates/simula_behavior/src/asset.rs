@@ -1,14 +1,20 @@
-use crate::{BehaviorChildren, BehaviorCursor, BehaviorFactory, BehaviorNode, BehaviorTree};
+use crate::{
+    blackboard::{self, Blackboard, BlackboardSchema},
+    decorators::SubtreeScope,
+    stop_and_despawn, BehaviorChildren, BehaviorCursor, BehaviorFactory, BehaviorFailure,
+    BehaviorNode, BehaviorRunning, BehaviorSuccess, BehaviorTree, BehaviorType,
+};
 use bevy::{
-    asset::{AssetLoader, LoadContext, LoadedAsset},
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    ecs::query::WorldQuery,
     prelude::*,
-    reflect::TypeUuid,
-    utils::BoxedFuture,
+    reflect::{ReflectRef, TypeUuid},
+    utils::{BoxedFuture, HashMap},
 };
 use serde::{Deserialize, Serialize};
 use simula_script::ScriptContext;
 use std::borrow::Cow;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 /// This is the one and only data type for creating behaviors.
 /// The idea is to have an extremely simple data type that can be serialized,
@@ -59,6 +65,155 @@ where
     }
 }
 
+/// A behavior document with a blackboard schema. Documents can also be
+/// a bare `Behavior`, with no blackboard schema.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+pub struct BehaviorFile<T: BehaviorFactory> {
+    #[serde(default)]
+    pub blackboard: BlackboardSchema,
+    pub root: Behavior<T>,
+}
+
+impl<T> BehaviorFile<T>
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    /// Parse a behavior document, with or without a blackboard schema. If
+    /// both fail, the error is from the format that parsed the furthest, which
+    /// is the format the document is in.
+    pub fn from_document(document: &str) -> Result<Self, ron::error::SpannedError> {
+        let behavior_err = match ron::de::from_str::<Behavior<T>>(document) {
+            Ok(root) => {
+                return Ok(Self {
+                    blackboard: BlackboardSchema::default(),
+                    root,
+                })
+            }
+            Err(err) => err,
+        };
+        ron::de::from_str::<BehaviorFile<T>>(document).map_err(|file_err| {
+            let position = |err: &ron::error::SpannedError| (err.position.line, err.position.col);
+            if position(&file_err) > position(&behavior_err) {
+                file_err
+            } else {
+                behavior_err
+            }
+        })
+    }
+}
+
+/// Error found while inlining subtrees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BehaviorInlineError {
+    /// Subtree asset is not loaded yet
+    Loading(String),
+    /// Subtree asset failed to load or to deserialize
+    Invalid { asset: String, message: String },
+    /// Subtree references itself, the assets from the first reference to the
+    /// repeated one
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for BehaviorInlineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BehaviorInlineError::Loading(asset) => {
+                write!(f, "Subtree asset `{}` is still loading", asset)
+            }
+            BehaviorInlineError::Invalid { asset, message } => {
+                write!(f, "Subtree asset `{}` is invalid: {}", asset, message)
+            }
+            BehaviorInlineError::Cycle(assets) => {
+                write!(f, "Subtree references itself: {}", assets.join(" -> "))
+            }
+        }
+    }
+}
+
+/// Get the asset of a `Subtree` node
+pub fn subtree_asset<T: BehaviorFactory>(data: &T) -> Option<&str> {
+    if data.typ() != BehaviorType::Subtree {
+        return None;
+    }
+    match data.inner_reflect().reflect_ref() {
+        ReflectRef::Struct(subtree) => subtree
+            .field("asset")
+            .and_then(|asset| asset.downcast_ref::<Cow<'static, str>>())
+            .map(|asset| asset.as_ref()),
+        _ => None,
+    }
+}
+
+// Check if a `Subtree` node uses the blackboard of its tree
+fn subtree_shared<T: BehaviorFactory>(data: &T) -> bool {
+    match data.inner_reflect().reflect_ref() {
+        ReflectRef::Struct(subtree) => subtree
+            .field("scope")
+            .and_then(|scope| scope.downcast_ref::<SubtreeScope>())
+            .map_or(false, |scope| *scope == SubtreeScope::Shared),
+        _ => false,
+    }
+}
+
+/// Replace every shared `Subtree` node with no children by a `Subtree` node with
+/// the root of its asset as child, recursively. `asset` is the asset of `file`,
+/// if any, and `load` gets the document of a subtree asset. Blackboard keys of
+/// the subtrees are added to the schema, inlined subtrees use the blackboard
+/// of the tree. Isolated subtrees have their own blackboard, they are left to
+/// load when they run.
+pub fn inline_subtrees<T, F>(
+    file: &BehaviorFile<T>,
+    asset: Option<&str>,
+    mut load: F,
+) -> Result<BehaviorFile<T>, BehaviorInlineError>
+where
+    T: BehaviorFactory,
+    F: FnMut(&str) -> Result<BehaviorFile<T>, BehaviorInlineError>,
+{
+    let mut file = file.clone();
+    let mut assets: Vec<String> = asset.into_iter().map(|asset| asset.to_string()).collect();
+    inline_node(&mut file.root, &mut file.blackboard, &mut assets, &mut load)?;
+    Ok(file)
+}
+
+fn inline_node<T, F>(
+    behavior: &mut Behavior<T>,
+    blackboard: &mut BlackboardSchema,
+    assets: &mut Vec<String>,
+    load: &mut F,
+) -> Result<(), BehaviorInlineError>
+where
+    T: BehaviorFactory,
+    F: FnMut(&str) -> Result<BehaviorFile<T>, BehaviorInlineError>,
+{
+    let asset = subtree_asset(behavior.data()).map(|asset| asset.to_string());
+    match asset {
+        Some(asset) if behavior.nodes().is_empty() && subtree_shared(behavior.data()) => {
+            if let Some(index) = assets.iter().position(|other| *other == asset) {
+                let mut cycle = assets[index..].to_vec();
+                cycle.push(asset);
+                return Err(BehaviorInlineError::Cycle(cycle));
+            }
+            let mut subtree = load(&asset)?;
+            assets.push(asset);
+            inline_node(&mut subtree.root, &mut subtree.blackboard, assets, load)?;
+            assets.pop();
+            for key in subtree.blackboard.0 {
+                if blackboard.get(&key.key).is_none() {
+                    blackboard.push(key);
+                }
+            }
+            behavior.nodes_mut().push(subtree.root);
+        }
+        _ => {
+            for child in behavior.nodes_mut() {
+                inline_node(child, blackboard, assets, load)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Default, Debug, TypeUuid, Deserialize)]
 #[uuid = "B543FD10-86EA-42A6-BC87-2A9DB57BFBAD"]
 
@@ -67,6 +222,8 @@ where
     T: BehaviorFactory,
 {
     pub behavior: Behavior<T>,
+    #[serde(default)]
+    pub blackboard: BlackboardSchema,
     pub file_name: Option<Cow<'static, str>>,
 }
 
@@ -74,6 +231,13 @@ where
 #[uuid = "7f117190-5353-11ed-ae42-02a179e5df2b"]
 pub struct BehaviorDocument(String);
 
+impl BehaviorDocument {
+    /// Document from RON text, like the contents of a `.bht.ron` file
+    pub fn new(document: impl Into<String>) -> Self {
+        Self(document.into())
+    }
+}
+
 #[derive(Default)]
 pub struct BehaviorAssetLoader;
 
@@ -96,6 +260,74 @@ impl AssetLoader for BehaviorAssetLoader {
     }
 }
 
+/// Document a behavior tree was loaded from, kept to reload the tree when the
+/// document changes
+#[derive(Component, Debug, Clone, Deref)]
+pub struct BehaviorTreeSource(pub Handle<BehaviorDocument>);
+
+/// How behavior trees are rebuilt when their document or asset changes
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorHotReload {
+    /// Keep running the old tree
+    Disabled,
+    /// Rebuild the tree and run it from the root, the blackboard is kept
+    #[default]
+    Restart,
+    /// Rebuild the tree and keep the state of the nodes found at the same path
+    /// of names from the root. Restarts if a node with the cursor is not found.
+    PreserveCursor,
+}
+
+/// How `Subtree` nodes get the tree of their asset
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorSubtrees {
+    /// Load the subtree asset when the node first runs
+    #[default]
+    Runtime,
+    /// Inline the subtree assets when the tree document is loaded, the tree
+    /// starts once all of them are loaded. Recursive subtrees are errors.
+    Inline,
+}
+
+/// Subtree documents inlined in a behavior tree, kept loaded while the tree
+/// waits for them, and to reload the tree when one of them changes
+#[derive(Component, Debug, Default, Clone, Deref)]
+pub struct BehaviorInlineDocuments(pub Vec<Handle<BehaviorDocument>>);
+
+/// Added to a tree reloaded while some of its subtree documents are loading,
+/// the tree is reloaded once they are
+#[derive(Component, Debug, Default, Clone)]
+pub struct BehaviorInlineReload;
+
+/// State of a node before its tree was rebuilt
+#[derive(Debug, Default, Clone)]
+pub struct BehaviorNodeRestore {
+    /// Names of the nodes from the root to this node
+    pub path: Vec<String>,
+    pub cursor: bool,
+    pub running: bool,
+    pub success: bool,
+    pub failure: bool,
+}
+
+/// Added with `BehaviorTreeReset` to restore the state of the nodes once the
+/// tree is rebuilt. The tree stays idle if no node has the cursor.
+#[derive(Component, Debug, Default, Clone)]
+pub struct BehaviorTreeRestore {
+    pub nodes: Vec<BehaviorNodeRestore>,
+}
+
+/// Query for the state of behavior nodes
+#[derive(WorldQuery)]
+pub struct BehaviorNodeStateQuery {
+    name: &'static Name,
+    children: Option<&'static BehaviorChildren>,
+    cursor: Option<&'static BehaviorCursor>,
+    running: Option<&'static BehaviorRunning>,
+    success: Option<&'static BehaviorSuccess>,
+    failure: Option<&'static BehaviorFailure>,
+}
+
 #[derive(Component, Default)]
 pub struct BehaviorTreeReset<T>
 where
@@ -107,14 +339,20 @@ where
 pub fn behavior_tree_reset<T>(
     mut commands: Commands,
     behavior_assets: Res<Assets<BehaviorAsset<T>>>,
+    mut script_ctxs: ResMut<Assets<ScriptContext>>,
     loadings: Query<
-        (Entity, &Handle<BehaviorAsset<T>>, Option<&BehaviorNode>),
+        (
+            Entity,
+            &Handle<BehaviorAsset<T>>,
+            Option<&Handle<ScriptContext>>,
+            Option<&BehaviorNode>,
+        ),
         (With<BehaviorTree<T>>, With<BehaviorTreeReset<T>>),
     >,
 ) where
     T: BehaviorFactory + for<'de> Deserialize<'de>,
 {
-    for (entity, behavior_asset, behavior_node) in loadings.iter() {
+    for (entity, behavior_asset, script_ctx_handle, behavior_node) in loadings.iter() {
         // Remove behavior tree child nodes
         commands.entity(entity).clear_children();
 
@@ -123,6 +361,16 @@ pub fn behavior_tree_reset<T>(
             info!("Loading behavior tree for entity {:?}", entity);
             commands.entity(entity).remove::<BehaviorTreeReset<T>>();
 
+            // Add blackboard defaults, values already in the blackboard are kept
+            if let Some(script_ctx) =
+                script_ctx_handle.and_then(|handle| script_ctxs.get_mut(handle))
+            {
+                blackboard::apply_schema(script_ctx, &behavior_asset.blackboard);
+            }
+            commands
+                .entity(entity)
+                .insert(Blackboard::new(behavior_asset.blackboard.clone()));
+
             // if this behavior tree is a behavior node, then it will be a parent for the loaded tree root
             // this is for linking the trees together
             let parent = if behavior_node.is_some() {
@@ -149,25 +397,71 @@ pub fn behavior_tree_reset<T>(
 
 pub fn behavior_document_to_asset<T>(
     mut commands: Commands,
+    subtrees: Res<BehaviorSubtrees>,
     behavior_documents: Res<Assets<BehaviorDocument>>,
     mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
     mut script_ctxs: ResMut<Assets<ScriptContext>>,
     asset_server: Res<AssetServer>,
-    documents: Query<(Entity, &Handle<BehaviorDocument>), With<BehaviorTree<T>>>,
+    documents: Query<
+        (
+            Entity,
+            &Handle<BehaviorDocument>,
+            Option<&Handle<ScriptContext>>,
+            Option<&BehaviorInlineDocuments>,
+        ),
+        With<BehaviorTree<T>>,
+    >,
 ) where
     T: BehaviorFactory + for<'de> Deserialize<'de>,
 {
-    for (entity, behavior_document_handle) in documents.iter() {
+    for (entity, behavior_document_handle, script_ctx_handle, inline_documents) in documents.iter()
+    {
+        // Wait for the subtree documents requested, before parsing again
+        if let Some(inline_documents) = inline_documents {
+            if documents_loading(&asset_server, &behavior_documents, inline_documents) {
+                continue;
+            }
+        }
+
         // Convert document into behavior asset
         if let Some(behavior_document) = behavior_documents.get(behavior_document_handle) {
+            // Deserialize behavior asset
+            let path = asset_server.get_handle_path(behavior_document_handle);
+            let res =
+                BehaviorFile::<T>::from_document(&behavior_document).map_err(|err| err.to_string());
+
+            // Inline subtrees, keep trying until all subtree documents are loaded
+            let res = match res {
+                Ok(behavior_file) if *subtrees == BehaviorSubtrees::Inline => {
+                    let asset = path
+                        .as_ref()
+                        .map(|path| path.path().to_string_lossy().to_string());
+                    let (res, handles) = inline_loaded_subtrees(
+                        &behavior_file,
+                        asset.as_deref(),
+                        &asset_server,
+                        &behavior_documents,
+                    );
+                    // Keep the subtree documents, to reload the tree when they change
+                    commands
+                        .entity(entity)
+                        .insert(BehaviorInlineDocuments(handles));
+                    if let Err(BehaviorInlineError::Loading(_)) = res {
+                        continue;
+                    }
+                    res.map_err(|err| err.to_string())
+                }
+                res => res,
+            };
+
             // Remove document handle, if it fails to deserialize we wont keep trying
-            commands.entity(entity).remove::<Handle<BehaviorDocument>>();
+            commands
+                .entity(entity)
+                .remove::<Handle<BehaviorDocument>>()
+                .insert(BehaviorTreeSource(behavior_document_handle.clone()));
 
-            // Deserialize behavior asset
-            let res = ron::de::from_str::<Behavior<T>>(&behavior_document);
-            if let Ok(behavior) = res {
+            if let Ok(behavior_file) = res {
                 // Get file name
-                let path = asset_server.get_handle_path(behavior_document_handle);
                 let file_name = path.and_then(|path| {
                     let file_path = path.path().to_string_lossy();
                     let file_name: Cow<'static, str> =
@@ -177,13 +471,15 @@ pub fn behavior_document_to_asset<T>(
 
                 // Add behavior asset to asset manager
                 let behavior_handle = behavior_assets.add(BehaviorAsset {
-                    behavior,
+                    behavior: behavior_file.root,
+                    blackboard: behavior_file.blackboard,
                     file_name,
                 });
 
-                // Create scripting scope
-                let script_ctx = BehaviorTree::<T>::create_script_context();
-                let script_ctx_handle = script_ctxs.add(script_ctx);
+                // Create scripting scope, unless the tree has one already
+                let script_ctx_handle = script_ctx_handle
+                    .cloned()
+                    .unwrap_or_else(|| script_ctxs.add(BehaviorTree::<T>::create_script_context()));
 
                 // and insert
                 commands
@@ -192,10 +488,394 @@ pub fn behavior_document_to_asset<T>(
                     .insert(script_ctx_handle);
             } else if let Err(err) = res {
                 error!(
-                    "Failed to deserialize behavior tree for entity {:?} {}",
+                    "Failed to load behavior tree for entity {:?} {}",
                     entity, err
                 );
             }
         }
     }
 }
+
+// Get the assets of the `Subtree` nodes with no children
+fn subtree_assets<T: BehaviorFactory>(behavior: &Behavior<T>, assets: &mut Vec<String>) {
+    match subtree_asset(behavior.data()) {
+        Some(asset) if behavior.nodes().is_empty() => assets.push(asset.to_string()),
+        _ => {
+            for child in behavior.nodes() {
+                subtree_assets(child, assets);
+            }
+        }
+    }
+}
+
+// Inline the subtrees of `file` once all their documents are loaded. Every
+// subtree document found so far is requested at once and parsed once, their
+// handles are returned to keep them loaded.
+fn inline_loaded_subtrees<T>(
+    file: &BehaviorFile<T>,
+    asset: Option<&str>,
+    asset_server: &AssetServer,
+    behavior_documents: &Assets<BehaviorDocument>,
+) -> (
+    Result<BehaviorFile<T>, BehaviorInlineError>,
+    Vec<Handle<BehaviorDocument>>,
+)
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    let mut handles = vec![];
+    let mut files: HashMap<String, Result<BehaviorFile<T>, BehaviorInlineError>> = default();
+    let mut loading = None;
+    let mut pending = vec![];
+    subtree_assets(&file.root, &mut pending);
+    while let Some(subtree) = pending.pop() {
+        if files.contains_key(&subtree) {
+            continue;
+        }
+        let handle = asset_server.load(subtree.as_str());
+        let res = load_subtree_document(asset_server, behavior_documents, &handle, &subtree);
+        handles.push(handle);
+        match &res {
+            Ok(subtree_file) => subtree_assets(&subtree_file.root, &mut pending),
+            Err(err @ BehaviorInlineError::Loading(_)) => loading = Some(err.clone()),
+            Err(_) => {}
+        }
+        files.insert(subtree, res);
+    }
+    if let Some(err) = loading {
+        return (Err(err), handles);
+    }
+    let res = inline_subtrees(file, asset, |subtree| {
+        files
+            .get(subtree)
+            .cloned()
+            .unwrap_or_else(|| Err(BehaviorInlineError::Loading(subtree.to_string())))
+    });
+    (res, handles)
+}
+
+// Some subtree documents are still loading
+fn documents_loading(
+    asset_server: &AssetServer,
+    behavior_documents: &Assets<BehaviorDocument>,
+    inline_documents: &BehaviorInlineDocuments,
+) -> bool {
+    inline_documents.iter().any(|handle| {
+        behavior_documents.get(handle).is_none()
+            && asset_server.get_load_state(handle) != LoadState::Failed
+    })
+}
+
+// Get a loaded subtree document
+fn load_subtree_document<T>(
+    asset_server: &AssetServer,
+    behavior_documents: &Assets<BehaviorDocument>,
+    handle: &Handle<BehaviorDocument>,
+    asset: &str,
+) -> Result<BehaviorFile<T>, BehaviorInlineError>
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    match behavior_documents.get(handle) {
+        Some(document) => {
+            BehaviorFile::<T>::from_document(document).map_err(|err| BehaviorInlineError::Invalid {
+                asset: asset.to_string(),
+                message: err.to_string(),
+            })
+        }
+        None if asset_server.get_load_state(handle) == LoadState::Failed => {
+            Err(BehaviorInlineError::Invalid {
+                asset: asset.to_string(),
+                message: "failed to load".to_string(),
+            })
+        }
+        None => Err(BehaviorInlineError::Loading(asset.to_string())),
+    }
+}
+
+/// Update the behavior assets of trees when the document they were loaded from
+/// changes, or with `BehaviorSubtrees::Inline` one of their subtree documents.
+/// Documents that fail to deserialize keep the old behavior.
+pub fn behavior_document_reload<T>(
+    mut commands: Commands,
+    hot_reload: Res<BehaviorHotReload>,
+    subtrees: Res<BehaviorSubtrees>,
+    mut document_events: EventReader<AssetEvent<BehaviorDocument>>,
+    behavior_documents: Res<Assets<BehaviorDocument>>,
+    mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
+    asset_server: Res<AssetServer>,
+    trees: Query<
+        (
+            Entity,
+            &BehaviorTreeSource,
+            &Handle<BehaviorAsset<T>>,
+            Option<&BehaviorInlineDocuments>,
+            Option<&BehaviorInlineReload>,
+        ),
+        With<BehaviorTree<T>>,
+    >,
+) where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    let modified: Vec<&Handle<BehaviorDocument>> = document_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle),
+            _ => None,
+        })
+        .collect();
+    if *hot_reload == BehaviorHotReload::Disabled {
+        return;
+    }
+
+    // Trees can share a behavior asset, update it once
+    let mut updated: Vec<&Handle<BehaviorAsset<T>>> = vec![];
+    for (entity, source, behavior_asset_handle, inline_documents, inline_reload) in &trees {
+        let inline_modified = inline_documents.map_or(false, |inline_documents| {
+            inline_documents
+                .iter()
+                .any(|handle| modified.contains(&handle))
+        });
+        let reload = inline_reload.is_some() || inline_modified || modified.contains(&&**source);
+        if !reload || updated.contains(&behavior_asset_handle) {
+            continue;
+        }
+        // Wait for the subtree documents requested by a previous reload
+        if let (Some(inline_documents), Some(_)) = (inline_documents, inline_reload) {
+            if documents_loading(&asset_server, &behavior_documents, inline_documents) {
+                continue;
+            }
+        }
+        commands.entity(entity).remove::<BehaviorInlineReload>();
+        let Some(behavior_document) = behavior_documents.get(&**source) else {
+            continue;
+        };
+        let behavior_file = match BehaviorFile::<T>::from_document(behavior_document) {
+            Ok(behavior_file) => behavior_file,
+            Err(err) => {
+                error!("Failed to deserialize modified behavior tree {}", err);
+                continue;
+            }
+        };
+
+        // Subtrees are inlined again, new subtree documents may still be loading
+        let behavior_file = if *subtrees == BehaviorSubtrees::Inline {
+            let asset = asset_server
+                .get_handle_path(&**source)
+                .map(|path| path.path().to_string_lossy().to_string());
+            let (res, handles) = inline_loaded_subtrees(
+                &behavior_file,
+                asset.as_deref(),
+                &asset_server,
+                &behavior_documents,
+            );
+            commands
+                .entity(entity)
+                .insert(BehaviorInlineDocuments(handles));
+            match res {
+                Ok(behavior_file) => behavior_file,
+                Err(BehaviorInlineError::Loading(_)) => {
+                    commands.entity(entity).insert(BehaviorInlineReload);
+                    continue;
+                }
+                Err(err) => {
+                    error!("Failed to inline modified behavior tree {}", err);
+                    continue;
+                }
+            }
+        } else {
+            behavior_file
+        };
+
+        updated.push(behavior_asset_handle);
+        if let Some(behavior_asset) = behavior_assets.get_mut(behavior_asset_handle) {
+            behavior_asset.behavior = behavior_file.root;
+            behavior_asset.blackboard = behavior_file.blackboard;
+        }
+    }
+}
+
+/// Rebuild behavior trees when their behavior asset changes, as set by
+/// `BehaviorHotReload`
+pub fn behavior_asset_reload<T>(
+    mut commands: Commands,
+    hot_reload: Res<BehaviorHotReload>,
+    mut asset_events: EventReader<AssetEvent<BehaviorAsset<T>>>,
+    trees: Query<
+        (
+            Entity,
+            &Handle<BehaviorAsset<T>>,
+            Option<&Children>,
+            Option<&BehaviorNode>,
+            Option<&BehaviorRunning>,
+        ),
+        (With<BehaviorTree<T>>, Without<BehaviorTreeReset<T>>),
+    >,
+    nodes: Query<BehaviorNodeStateQuery, With<BehaviorNode>>,
+    running_nodes: Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+) where
+    T: BehaviorFactory,
+{
+    let mut modified = vec![];
+    for event in asset_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if !modified.contains(&handle) {
+                modified.push(handle);
+            }
+        }
+    }
+    if *hot_reload == BehaviorHotReload::Disabled {
+        return;
+    }
+
+    for (entity, behavior_asset, children, behavior_node, running) in &trees {
+        if !modified.contains(&behavior_asset) {
+            continue;
+        }
+        info!("Reloading behavior tree for entity {:?}", entity);
+        let root = children.and_then(|children| children.first().copied());
+
+        let restore = match *hot_reload {
+            BehaviorHotReload::PreserveCursor => {
+                let mut restore = BehaviorTreeRestore::default();
+                if let Some(root) = root {
+                    record_nodes(root, &mut vec![], &nodes, &mut restore.nodes);
+                }
+                Some(restore)
+            }
+            // A subtree that is not running stays idle
+            _ if behavior_node.is_some() && running.is_none() => {
+                Some(BehaviorTreeRestore::default())
+            }
+            _ => None,
+        };
+
+        // Old nodes are stopped and despawned, not just detached, so running
+        // nodes see `BehaviorStopped` and release what they hold
+        if let Some(root) = root {
+            stop_and_despawn(&mut commands, root, root, &running_nodes);
+        }
+        commands
+            .entity(entity)
+            .insert(BehaviorTreeReset::<T>::default());
+        if let Some(restore) = restore {
+            commands.entity(entity).insert(restore);
+        }
+    }
+}
+
+// Record the state of a node and its children
+fn record_nodes(
+    entity: Entity,
+    path: &mut Vec<String>,
+    nodes: &Query<BehaviorNodeStateQuery, With<BehaviorNode>>,
+    restored: &mut Vec<BehaviorNodeRestore>,
+) {
+    let Ok(node) = nodes.get(entity) else {
+        return;
+    };
+    path.push(node.name.to_string());
+    if node.cursor.is_some()
+        || node.running.is_some()
+        || node.success.is_some()
+        || node.failure.is_some()
+    {
+        restored.push(BehaviorNodeRestore {
+            path: path.clone(),
+            cursor: node.cursor.is_some(),
+            running: node.running.is_some(),
+            success: node.success.is_some(),
+            failure: node.failure.is_some(),
+        });
+    }
+    if let Some(children) = node.children {
+        for child in children.iter() {
+            record_nodes(*child, path, nodes, restored);
+        }
+    }
+    path.pop();
+}
+
+// Find a node by the names of the nodes from the root, the first child with a
+// matching name is followed
+fn find_node(
+    root: Entity,
+    path: &[String],
+    nodes: &Query<(&Name, Option<&BehaviorChildren>), With<BehaviorNode>>,
+) -> Option<Entity> {
+    let (name, rest) = path.split_first()?;
+    let (root_name, _) = nodes.get(root).ok()?;
+    if root_name.as_str() != name {
+        return None;
+    }
+    rest.iter().try_fold(root, |entity, name| {
+        let (_, children) = nodes.get(entity).ok()?;
+        children?.iter().copied().find(|child| {
+            nodes
+                .get(*child)
+                .map_or(false, |(child_name, _)| child_name.as_str() == name)
+        })
+    })
+}
+
+/// Restore the state of rebuilt behavior trees with a `BehaviorTreeRestore`
+pub fn behavior_tree_restore<T>(
+    mut commands: Commands,
+    restores: Query<
+        (Entity, &BehaviorTreeRestore, &Children),
+        (With<BehaviorTree<T>>, Without<BehaviorTreeReset<T>>),
+    >,
+    nodes: Query<(&Name, Option<&BehaviorChildren>), With<BehaviorNode>>,
+) where
+    T: BehaviorFactory,
+{
+    for (entity, restore, children) in &restores {
+        commands.entity(entity).remove::<BehaviorTreeRestore>();
+        let Some(root) = children.first() else {
+            continue;
+        };
+
+        let found: Vec<Option<Entity>> = restore
+            .nodes
+            .iter()
+            .map(|node| find_node(*root, &node.path, &nodes))
+            .collect();
+        let lost = restore
+            .nodes
+            .iter()
+            .zip(&found)
+            .any(|(node, found)| node.cursor && found.is_none());
+        if lost {
+            info!(
+                "Cursor not found, restarting behavior tree for entity {:?}",
+                entity
+            );
+            continue;
+        }
+
+        // Nodes with the cursor start again, nodes above them keep running
+        commands.entity(*root).remove::<BehaviorCursor>();
+        for (node, found) in restore.nodes.iter().zip(found) {
+            let Some(found) = found else {
+                continue;
+            };
+            let mut node_commands = commands.entity(found);
+            if node.cursor {
+                node_commands.insert(BehaviorCursor::Delegate);
+                continue;
+            }
+            if node.running {
+                node_commands.insert(BehaviorRunning);
+            }
+            if node.success {
+                node_commands.insert(BehaviorSuccess);
+            }
+            if node.failure {
+                node_commands.insert(BehaviorFailure);
+            }
+        }
+    }
+}
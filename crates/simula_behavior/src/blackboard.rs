@@ -0,0 +1,438 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+use simula_script::{
+    script::{self, RegisterFn},
+    ScriptContext,
+};
+use std::{borrow::Cow, cmp::Ordering};
+
+/// Name of the blackboard map in the script scope
+pub const BLACKBOARD: &str = "blackboard";
+
+/// A typed blackboard value, the variant is the type of the key
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+pub enum BlackboardValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(Cow<'static, str>),
+}
+
+impl Default for BlackboardValue {
+    fn default() -> Self {
+        Self::Int(0)
+    }
+}
+
+impl BlackboardValue {
+    /// Convert into a script value
+    pub fn to_dynamic(&self) -> script::Dynamic {
+        match self {
+            BlackboardValue::Bool(value) => script::Dynamic::from(*value),
+            BlackboardValue::Int(value) => script::Dynamic::from(*value),
+            BlackboardValue::Float(value) => script::Dynamic::from(*value),
+            BlackboardValue::Str(value) => script::Dynamic::from(value.to_string()),
+        }
+    }
+
+    /// Convert from a script value, if it has a blackboard type
+    pub fn from_dynamic(value: &script::Dynamic) -> Option<Self> {
+        if value.is::<BlackboardValue>() {
+            value.clone().try_cast::<BlackboardValue>()
+        } else if value.is::<bool>() {
+            value.clone().try_cast::<bool>().map(BlackboardValue::Bool)
+        } else if value.is::<i64>() {
+            value.clone().try_cast::<i64>().map(BlackboardValue::Int)
+        } else if value.is::<f64>() {
+            value.clone().try_cast::<f64>().map(BlackboardValue::Float)
+        } else {
+            value
+                .clone()
+                .try_cast::<String>()
+                .map(|value| BlackboardValue::Str(value.into()))
+        }
+    }
+
+    /// Check if two values have the same type
+    pub fn same_type(&self, other: &BlackboardValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Add two numbers, an int added to a float is a float
+    pub fn add(&self, other: &BlackboardValue) -> Option<BlackboardValue> {
+        match (self, other) {
+            (BlackboardValue::Int(a), BlackboardValue::Int(b)) => {
+                Some(BlackboardValue::Int(a.wrapping_add(*b)))
+            }
+            (BlackboardValue::Float(a), BlackboardValue::Float(b)) => {
+                Some(BlackboardValue::Float(a + b))
+            }
+            (BlackboardValue::Float(a), BlackboardValue::Int(b)) => {
+                Some(BlackboardValue::Float(a + *b as f64))
+            }
+            (BlackboardValue::Int(a), BlackboardValue::Float(b)) => {
+                Some(BlackboardValue::Float(*a as f64 + b))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare two values of the same type, ints and floats can be compared
+    pub fn compare(&self, other: &BlackboardValue) -> Option<Ordering> {
+        match (self, other) {
+            (BlackboardValue::Bool(a), BlackboardValue::Bool(b)) => a.partial_cmp(b),
+            (BlackboardValue::Int(a), BlackboardValue::Int(b)) => a.partial_cmp(b),
+            (BlackboardValue::Float(a), BlackboardValue::Float(b)) => a.partial_cmp(b),
+            (BlackboardValue::Float(a), BlackboardValue::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (BlackboardValue::Int(a), BlackboardValue::Float(b)) => (*a as f64).partial_cmp(b),
+            (BlackboardValue::Str(a), BlackboardValue::Str(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for BlackboardValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for BlackboardValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for BlackboardValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for BlackboardValue {
+    fn from(value: String) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl From<&'static str> for BlackboardValue {
+    fn from(value: &'static str) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl TryFrom<BlackboardValue> for bool {
+    type Error = BlackboardValue;
+    fn try_from(value: BlackboardValue) -> Result<Self, Self::Error> {
+        match value {
+            BlackboardValue::Bool(value) => Ok(value),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<BlackboardValue> for i64 {
+    type Error = BlackboardValue;
+    fn try_from(value: BlackboardValue) -> Result<Self, Self::Error> {
+        match value {
+            BlackboardValue::Int(value) => Ok(value),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<BlackboardValue> for f64 {
+    type Error = BlackboardValue;
+    fn try_from(value: BlackboardValue) -> Result<Self, Self::Error> {
+        match value {
+            BlackboardValue::Float(value) => Ok(value),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<BlackboardValue> for String {
+    type Error = BlackboardValue;
+    fn try_from(value: BlackboardValue) -> Result<Self, Self::Error> {
+        match value {
+            BlackboardValue::Str(value) => Ok(value.into_owned()),
+            _ => Err(value),
+        }
+    }
+}
+
+/// A key declared in a blackboard schema, with its default value
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+pub struct BlackboardKey {
+    pub key: Cow<'static, str>,
+    pub default: BlackboardValue,
+}
+
+/// Blackboard keys declared by a behavior document
+#[derive(
+    Debug, Default, Clone, PartialEq, Reflect, FromReflect, Serialize, Deserialize, Deref, DerefMut,
+)]
+pub struct BlackboardSchema(pub Vec<BlackboardKey>);
+
+impl BlackboardSchema {
+    /// Get the declared key
+    pub fn get(&self, key: &str) -> Option<&BlackboardKey> {
+        self.iter().find(|entry| entry.key == key)
+    }
+}
+
+/// A copy of all blackboard values of a tree, to be restored later
+#[derive(Debug, Default, Clone, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+pub struct BlackboardSnapshot(pub Vec<(Cow<'static, str>, BlackboardValue)>);
+
+impl BlackboardSnapshot {
+    /// Snapshot the blackboard values in a script context
+    pub fn from_script_context(script_ctx: &ScriptContext) -> Self {
+        Self(read_values(script_ctx))
+    }
+
+    /// Replace the blackboard values in a script context with the snapshot.
+    /// Values missing from the snapshot are removed, except values without a
+    /// blackboard type (arrays, maps, ...) which a snapshot cannot hold and
+    /// are kept as they are.
+    pub fn apply(&self, script_ctx: &mut ScriptContext) {
+        let mut blackboard = script_ctx
+            .scope
+            .get_value::<script::Map>(BLACKBOARD)
+            .unwrap_or_default();
+        blackboard.retain(|_, value| BlackboardValue::from_dynamic(value).is_none());
+        for (key, value) in &self.0 {
+            blackboard.insert(key.to_string().into(), value.to_dynamic());
+        }
+        script_ctx.scope.set_value(BLACKBOARD, blackboard);
+    }
+}
+
+/// A component added to behavior trees with a blackboard
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+#[reflect(Component)]
+pub struct Blackboard {
+    pub schema: BlackboardSchema,
+    /// Last known values, used to detect changes
+    #[reflect(ignore)]
+    values: Option<HashMap<String, BlackboardValue>>,
+}
+
+impl Blackboard {
+    pub fn new(schema: BlackboardSchema) -> Self {
+        Self {
+            schema,
+            values: None,
+        }
+    }
+}
+
+/// Event sent when a blackboard value changes, from scripts or from systems
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlackboardChanged {
+    pub tree: Entity,
+    pub key: Cow<'static, str>,
+    /// New value, `None` if the key was removed
+    pub value: Option<BlackboardValue>,
+}
+
+/// Register blackboard value constructors, so scripts can return typed values
+/// like `Int(blackboard.count + 1)`
+pub fn register_script_types(engine: &mut script::Engine) {
+    engine.register_type::<BlackboardValue>();
+    engine.register_fn("Bool", |value: bool| BlackboardValue::Bool(value));
+    engine.register_fn("Int", |value: i64| BlackboardValue::Int(value));
+    engine.register_fn("Float", |value: f64| BlackboardValue::Float(value));
+    engine.register_fn("Str", |value: script::ImmutableString| {
+        BlackboardValue::Str(value.to_string().into())
+    });
+}
+
+/// Create the blackboard map in a script context, or add any missing keys
+/// declared in the schema. Existing values are kept.
+pub fn apply_schema(script_ctx: &mut ScriptContext, schema: &BlackboardSchema) {
+    let mut blackboard = script_ctx
+        .scope
+        .get_value::<script::Map>(BLACKBOARD)
+        .unwrap_or_default();
+    for entry in schema.iter() {
+        if !blackboard.contains_key(entry.key.as_ref()) {
+            blackboard.insert(entry.key.to_string().into(), entry.default.to_dynamic());
+        }
+    }
+    script_ctx.scope.set_value(BLACKBOARD, blackboard);
+}
+
+fn read_values(script_ctx: &ScriptContext) -> Vec<(Cow<'static, str>, BlackboardValue)> {
+    let mut values: Vec<(Cow<'static, str>, BlackboardValue)> = script_ctx
+        .scope
+        .get_value::<script::Map>(BLACKBOARD)
+        .map(|blackboard| {
+            blackboard
+                .iter()
+                .filter_map(|(key, value)| {
+                    BlackboardValue::from_dynamic(value)
+                        .map(|value| (Cow::Owned(key.to_string()), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    values.sort_by(|(a, _), (b, _)| a.cmp(b));
+    values
+}
+
+// Blackboard values by key, unsorted
+fn read_value_map(script_ctx: &ScriptContext) -> HashMap<String, BlackboardValue> {
+    script_ctx
+        .scope
+        .get_value::<script::Map>(BLACKBOARD)
+        .map(|blackboard| {
+            blackboard
+                .iter()
+                .filter_map(|(key, value)| {
+                    BlackboardValue::from_dynamic(value).map(|value| (key.to_string(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get a blackboard value from a script context
+pub fn get_value(script_ctx: &ScriptContext, key: &str) -> Option<BlackboardValue> {
+    let blackboard = script_ctx.scope.get_value::<script::Map>(BLACKBOARD)?;
+    blackboard.get(key).and_then(BlackboardValue::from_dynamic)
+}
+
+/// Set a blackboard value in a script context, the value must match the type
+/// declared in the schema, if any
+pub fn set_value(
+    script_ctx: &mut ScriptContext,
+    schema: Option<&BlackboardSchema>,
+    key: &str,
+    value: BlackboardValue,
+) -> Result<(), String> {
+    if let Some(entry) = schema.and_then(|schema| schema.get(key)) {
+        if !entry.default.same_type(&value) {
+            return Err(format!(
+                "Blackboard key `{}` expects {:?}, got {:?}",
+                key, entry.default, value
+            ));
+        }
+    }
+    let mut blackboard = script_ctx
+        .scope
+        .get_value::<script::Map>(BLACKBOARD)
+        .unwrap_or_default();
+    blackboard.insert(key.to_string().into(), value.to_dynamic());
+    script_ctx.scope.set_value(BLACKBOARD, blackboard);
+    Ok(())
+}
+
+/// Typed access to tree blackboards from systems
+#[derive(SystemParam)]
+pub struct Blackboards<'w, 's> {
+    ctx_handles: Query<'w, 's, (&'static Handle<ScriptContext>, Option<&'static Blackboard>)>,
+    ctxs: ResMut<'w, Assets<ScriptContext>>,
+}
+
+impl<'w, 's> Blackboards<'w, 's> {
+    /// Get a blackboard value of a tree
+    pub fn get_value(&self, tree: Entity, key: &str) -> Option<BlackboardValue> {
+        let (ctx_handle, _) = self.ctx_handles.get(tree).ok()?;
+        let script_ctx = self.ctxs.get(ctx_handle)?;
+        get_value(script_ctx, key)
+    }
+
+    /// Get a typed blackboard value of a tree
+    pub fn get<V: TryFrom<BlackboardValue>>(&self, tree: Entity, key: &str) -> Option<V> {
+        self.get_value(tree, key)
+            .and_then(|value| V::try_from(value).ok())
+    }
+
+    /// Set a blackboard value of a tree, the value must match the type
+    /// declared in the schema, if any
+    pub fn set(
+        &mut self,
+        tree: Entity,
+        key: &str,
+        value: impl Into<BlackboardValue>,
+    ) -> Result<(), String> {
+        let (ctx_handle, blackboard) = self
+            .ctx_handles
+            .get(tree)
+            .map_err(|_| "Cannot find script context handle in tree entity".to_string())?;
+        let script_ctx = self
+            .ctxs
+            .get_mut(ctx_handle)
+            .ok_or_else(|| "Invalid script context handle".to_string())?;
+        let schema = blackboard.map(|blackboard| &blackboard.schema);
+        set_value(script_ctx, schema, key, value.into())
+    }
+
+    /// Take a snapshot of all blackboard values of a tree
+    pub fn snapshot(&self, tree: Entity) -> Option<BlackboardSnapshot> {
+        let (ctx_handle, _) = self.ctx_handles.get(tree).ok()?;
+        let script_ctx = self.ctxs.get(ctx_handle)?;
+        Some(BlackboardSnapshot::from_script_context(script_ctx))
+    }
+
+    /// Restore a snapshot of blackboard values into a tree, see
+    /// [`BlackboardSnapshot::apply`]
+    pub fn restore(&mut self, tree: Entity, snapshot: &BlackboardSnapshot) -> Result<(), String> {
+        let (ctx_handle, _) = self
+            .ctx_handles
+            .get(tree)
+            .map_err(|_| "Cannot find script context handle in tree entity".to_string())?;
+        let script_ctx = self
+            .ctxs
+            .get_mut(ctx_handle)
+            .ok_or_else(|| "Invalid script context handle".to_string())?;
+        snapshot.apply(script_ctx);
+        Ok(())
+    }
+}
+
+/// Send a `BlackboardChanged` event for every value that changed or was
+/// removed since the last check
+pub fn blackboard_changes(
+    mut blackboards: Query<(Entity, &mut Blackboard, &Handle<ScriptContext>)>,
+    script_ctxs: Res<Assets<ScriptContext>>,
+    mut changes: EventWriter<BlackboardChanged>,
+) {
+    for (tree, mut blackboard, script_ctx_handle) in &mut blackboards {
+        let Some(script_ctx) = script_ctxs.get(script_ctx_handle) else {
+            continue;
+        };
+        let values = read_value_map(script_ctx);
+        let changed = match &blackboard.values {
+            // First time we see this blackboard, nothing changed yet
+            None => true,
+            Some(previous_values) => {
+                let mut changed: Vec<(&String, Option<&BlackboardValue>)> = values
+                    .iter()
+                    .filter(|(key, value)| previous_values.get(*key) != Some(*value))
+                    .map(|(key, value)| (key, Some(value)))
+                    .chain(
+                        previous_values
+                            .keys()
+                            .filter(|key| !values.contains_key(*key))
+                            .map(|key| (key, None)),
+                    )
+                    .collect();
+                // Same order on every run
+                changed.sort_by(|(a, _), (b, _)| a.cmp(b));
+                for (key, value) in changed.iter() {
+                    changes.send(BlackboardChanged {
+                        tree,
+                        key: Cow::Owned(key.to_string()),
+                        value: value.cloned(),
+                    });
+                }
+                !changed.is_empty()
+            }
+        };
+        if changed {
+            blackboard.values = Some(values);
+        }
+    }
+}
@@ -1,22 +1,30 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// All will run all of its children in parallel until all of them succeed.
 /// If any of them fail, the All node will fail.
-#[derive(Default, Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Default,
+    Debug,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    icon = "⇉",
+    desc = "Run all of its children in parallel until all \
+        of them succeed. If any of them fail, the All node will fail."
+)]
 pub struct All;
 
-impl BehaviorSpec for All {
-    const TYPE: BehaviorType = BehaviorType::Composite;
-    const NAME: &'static str = "All";
-    const ICON: &'static str = "⇉";
-    const DESC: &'static str = "Run all of its children in parallel until all \
-        of them succeed. If any of them fail, the All node will fail.";
-}
-
-impl BehaviorUI for All {}
-
 pub fn run(
     mut commands: Commands,
     alls: Query<(Entity, &BehaviorChildren), (With<All>, BehaviorRunQuery)>,
@@ -1,22 +1,30 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// Any will run all of its children in parallel until one of them succeed.
 /// If all of them fail, the Any node will fail.
-#[derive(Default, Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Default,
+    Debug,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    icon = "⇉",
+    desc = "Run all of its children in parallel until one of them succeed. If all of them fail, \
+        the Any node will fail."
+)]
 pub struct Any;
 
-impl BehaviorSpec for Any {
-    const TYPE: BehaviorType = BehaviorType::Composite;
-    const NAME: &'static str = "Any";
-    const ICON: &'static str = "⇉";
-    const DESC: &'static str = "Run all of its children in parallel until one of them succeed. \
-        If all of them fail, the Any node will fail.";
-}
-
-impl BehaviorUI for Any {}
-
 pub fn run(
     mut commands: Commands,
     anys: Query<(Entity, &BehaviorChildren), (With<Any>, BehaviorRunQuery)>,
@@ -1,9 +1,21 @@
 pub mod all;
 pub mod any;
+pub mod parallel;
+pub mod random_selector;
+pub mod reactive_selector;
+pub mod reactive_sequencer;
 pub mod selector;
 pub mod sequencer;
+pub mod shuffled_sequencer;
+pub mod utility_selector;
 
 pub use all::All;
 pub use any::Any;
+pub use parallel::{Parallel, ParallelPolicy};
+pub use random_selector::RandomSelector;
+pub use reactive_selector::ReactiveSelector;
+pub use reactive_sequencer::ReactiveSequencer;
 pub use selector::Selector;
 pub use sequencer::Sequencer;
+pub use shuffled_sequencer::ShuffledSequencer;
+pub use utility_selector::UtilitySelector;
@@ -0,0 +1,178 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// What to do with children still running once a threshold is reached
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ParallelPolicy {
+    /// Complete right away, running children are stopped
+    #[default]
+    Stop,
+    /// Wait for running children to complete before completing
+    Wait,
+}
+
+/// Parallel will run all of its children in parallel until `success_threshold`
+/// children succeed, or `failure_threshold` children fail. A `success_threshold`
+/// of zero requires all children to succeed. A `failure_threshold` of zero fails
+/// as soon as the success threshold can no longer be reached.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    icon = "⇶",
+    desc = "Run all of its children in parallel until the success threshold or the failure \
+        threshold is reached. A success threshold of zero requires all children to succeed. A \
+        failure threshold of zero fails as soon as the success threshold can no longer be \
+        reached."
+)]
+pub struct Parallel {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub success_threshold: BehaviorPropGeneric<u64>,
+    #[serde(default)]
+    #[behavior(prop)]
+    pub failure_threshold: BehaviorPropGeneric<u64>,
+    #[serde(default)]
+    #[behavior(property)]
+    pub policy: ParallelPolicy,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    pub outcome: Option<bool>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut parallels: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Parallel,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<Parallel>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut parallel, node, started) in &mut parallels {
+        if let BehaviorPropValue::None = parallel.success_threshold.value {
+            let result = parallel.success_threshold.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = parallel.failure_threshold.value {
+            let result = parallel.failure_threshold.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if started.is_some() {
+            parallel.outcome = None;
+        }
+
+        if let (
+            BehaviorPropValue::Some(success_threshold),
+            BehaviorPropValue::Some(failure_threshold),
+        ) = (
+            parallel.success_threshold.value.clone(),
+            parallel.failure_threshold.value.clone(),
+        ) {
+            if children.is_empty() {
+                commands.entity(entity).insert(BehaviorSuccess);
+                continue;
+            }
+
+            // Resolve thresholds against the number of children
+            let count = children.len() as u64;
+            let success_threshold = if success_threshold == 0 {
+                count
+            } else {
+                success_threshold.min(count)
+            };
+            let failure_threshold = if failure_threshold == 0 {
+                count - success_threshold + 1
+            } else {
+                failure_threshold.min(count)
+            };
+
+            // Tally children states
+            let mut successes = 0;
+            let mut failures = 0;
+            let mut running = 0;
+            let mut ready = vec![];
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running,
+            } in nodes.iter_many(children.iter())
+            {
+                if child_success.is_some() {
+                    successes += 1;
+                } else if child_failure.is_some() {
+                    failures += 1;
+                } else if child_running.is_some() {
+                    running += 1;
+                } else {
+                    ready.push(child_entity);
+                }
+            }
+
+            // Once an outcome is reached it sticks, even if more children complete
+            if parallel.outcome.is_none() {
+                if successes >= success_threshold {
+                    parallel.outcome = Some(true);
+                } else if failures >= failure_threshold {
+                    parallel.outcome = Some(false);
+                } else if running == 0 && ready.is_empty() {
+                    // All children completed, but no threshold was reached
+                    parallel.outcome = Some(false);
+                }
+            }
+
+            match parallel.outcome {
+                Some(outcome) => {
+                    if parallel.policy == ParallelPolicy::Wait && running > 0 {
+                        // Wait for running children to return the cursor
+                        commands.entity(entity).remove::<BehaviorCursor>();
+                    } else if outcome {
+                        commands.entity(entity).insert(BehaviorSuccess);
+                    } else {
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                }
+                None => {
+                    // Children running or ready, pass on cursor to the ready ones
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    for child_entity in ready {
+                        commands
+                            .entity(child_entity)
+                            .insert(BehaviorCursor::Delegate);
+                    }
+                }
+            }
+        }
+    }
+}
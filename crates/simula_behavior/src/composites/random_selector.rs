@@ -0,0 +1,105 @@
+use crate::{prelude::*, random::shuffle_children};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use simula_core::prng::Prng;
+
+/// A random selector works like a selector, but visits its children in a random
+/// order every time it starts. Children with a higher weight tend to be visited
+/// first, children without a weight have a weight of one.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    name = "Random Selector",
+    icon = "🎲",
+    desc = "A Random Selector will visit its children in a random order and succeed if any of \
+        its children succeed, like a Selector. Children with a higher weight tend to be visited \
+        first. It will fail if all children fail."
+)]
+pub struct RandomSelector {
+    /// Weight of each child, in order
+    #[serde(default)]
+    #[behavior(property)]
+    pub weights: Vec<f32>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    order: Vec<Entity>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    rng: Option<Prng>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut selectors: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut RandomSelector,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    seed: Res<BehaviorSeed>,
+) {
+    for (entity, children, mut selector, node, started) in &mut selectors {
+        // Pick the order of the children when started
+        if started.is_some() || selector.order.len() != children.len() {
+            let selector = &mut *selector;
+            let rng = selector
+                .rng
+                .get_or_insert_with(|| seed.node_rng(node.tree, entity));
+            selector.order = shuffle_children(rng, children, &selector.weights);
+        }
+
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            let mut should_fail = true;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            } in nodes.iter_many(selector.order.iter())
+            {
+                if child_failure.is_some() {
+                    // Child failed, so we move to next child
+                } else if child_success.is_some() {
+                    // Child succeeded, so we succeed
+                    commands.entity(entity).insert(BehaviorSuccess);
+                    should_fail = false;
+                    break;
+                } else {
+                    // Child is ready, pass on cursor
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                    should_fail = false;
+                    break;
+                }
+            }
+            // If all children failed, complete with failure
+            if should_fail {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
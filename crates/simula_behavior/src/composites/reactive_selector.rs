@@ -0,0 +1,111 @@
+use crate::{prelude::*, recheck_guards, BehaviorTrace};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// A reactive selector works like a selector, but while a child is running it
+/// keeps re-evaluating the `Guard` children that come before it. If one of those
+/// guards passes, the running child is aborted and the guard branch is run instead.
+/// Only direct `Guard` children are re-evaluated, on a copy of their condition, so
+/// the guard nodes themselves are left untouched. A guard whose script errors does
+/// not pass.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    name = "Reactive Selector",
+    icon = "⁉",
+    desc = "A Reactive Selector will visit each child in order and succeed if any of its \
+        children succeed, like a Selector. While a child is running, the Guard children before \
+        it are re-evaluated every tick. If one of them passes, the running child is aborted and \
+        the higher priority branch is run instead."
+)]
+pub struct ReactiveSelector;
+
+pub fn run(
+    mut commands: Commands,
+    selectors: Query<
+        (Entity, &BehaviorChildren, Option<&BehaviorCursor>),
+        (With<ReactiveSelector>, BehaviorIdleQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    names: Query<&Name, With<BehaviorNode>>,
+    guards: Query<(&Guard, &BehaviorNode)>,
+    stoppables: Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+    mut scripts: ScriptQueries,
+    mut trace: Option<ResMut<BehaviorTrace>>,
+) {
+    for (entity, children, cursor) in &selectors {
+        if cursor.is_some() {
+            if children.is_empty() {
+                commands.entity(entity).insert(BehaviorSuccess);
+                continue;
+            }
+
+            let mut should_fail = true;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            } in nodes.iter_many(children.iter())
+            {
+                if child_failure.is_some() {
+                    // Child failed, so we move to next child
+                } else if child_success.is_some() {
+                    // Child succeeded, so we succeed
+                    commands.entity(entity).insert(BehaviorSuccess);
+                    should_fail = false;
+                    break;
+                } else {
+                    // Child is ready, pass on cursor
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                    should_fail = false;
+                    break;
+                }
+            }
+            // If all children failed, complete with failure
+            if should_fail {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            continue;
+        }
+
+        // Re-evaluate guards of higher priority, if one passes abort the
+        // running child and run the guard branch
+        let guard = recheck_guards(
+            &mut commands,
+            children,
+            true,
+            &nodes,
+            &names,
+            &guards,
+            &stoppables,
+            &mut scripts,
+            &mut trace,
+        );
+        if let Some(guard_entity) = guard {
+            commands
+                .entity(guard_entity)
+                .remove::<BehaviorFailure>()
+                .insert(BehaviorCursor::Delegate);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{prelude::*, recheck_guards, BehaviorTrace};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// A reactive sequence works like a sequence, but while a child is running it
+/// keeps re-evaluating the `Guard` children that come before it. If one of those
+/// guards no longer passes, the running child is aborted and the sequence fails.
+/// Only direct `Guard` children are re-evaluated, on a copy of their condition, so
+/// the guard nodes themselves are left untouched. A guard whose script errors no
+/// longer passes.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    name = "Reactive Sequencer",
+    icon = "⇢",
+    desc = "A Reactive Sequence will visit each child in order and succeed if all of its \
+        children succeed, like a Sequence. While a child is running, the Guard children before \
+        it are re-evaluated every tick. If one of them no longer passes, the running child is \
+        aborted and the sequence fails."
+)]
+pub struct ReactiveSequencer;
+
+pub fn run(
+    mut commands: Commands,
+    sequences: Query<
+        (Entity, &BehaviorChildren, Option<&BehaviorCursor>),
+        (With<ReactiveSequencer>, BehaviorIdleQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    names: Query<&Name, With<BehaviorNode>>,
+    guards: Query<(&Guard, &BehaviorNode)>,
+    stoppables: Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+    mut scripts: ScriptQueries,
+    mut trace: Option<ResMut<BehaviorTrace>>,
+) {
+    for (entity, children, cursor) in &sequences {
+        if cursor.is_some() {
+            if children.is_empty() {
+                commands.entity(entity).insert(BehaviorSuccess);
+                continue;
+            }
+
+            let mut should_succeed = true;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            } in nodes.iter_many(children.iter())
+            {
+                if child_failure.is_some() {
+                    // Child failed, so we fail
+                    commands.entity(entity).insert(BehaviorFailure);
+                    should_succeed = false;
+                    break;
+                } else if child_success.is_some() {
+                    // Child succeeded, so we move to next child
+                } else {
+                    // Child is ready, pass on cursor
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                    should_succeed = false;
+                    break;
+                }
+            }
+            // If all children succeed, complete with success
+            if should_succeed {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            continue;
+        }
+
+        // Re-evaluate guards that already passed, if one no longer passes
+        // abort the running child and fail
+        let guard = recheck_guards(
+            &mut commands,
+            children,
+            false,
+            &nodes,
+            &names,
+            &guards,
+            &stoppables,
+            &mut scripts,
+            &mut trace,
+        );
+        if guard.is_some() {
+            // Short circuit by forcing a cursor and fail
+            commands
+                .entity(entity)
+                .insert(BehaviorCursor::Return)
+                .insert(BehaviorFailure);
+        }
+    }
+}
@@ -2,17 +2,29 @@ use crate::prelude::*;
 use bevy::prelude::*;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// A selector will return a success if any of its children succeed and not process
 /// any further children. It will process the first child, and if it fails will
 /// process the second, until a success is reached, at which point it will instantly
 /// return success. It will fail if all children fail.
-#[derive(Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, BehaviorSpec, BehaviorUI,
+)]
+#[behavior(
+    composite,
+    icon = "❓",
+    desc = "A Selector will visit each child in order and succeed if any of its children succeed \
+        and not process any further children. It will process the first child, and if it fails \
+        will process the second, until a success is reached, at which point it will instantly \
+        return success. It will fail if all children fail."
+)]
 pub struct Selector {
     #[serde(default)]
     random: bool,
     #[serde(skip)]
     #[reflect(ignore)]
+    #[behavior(save)]
     pub seed: u64,
 }
 
@@ -25,19 +37,6 @@ impl Default for Selector {
     }
 }
 
-impl BehaviorSpec for Selector {
-    const TYPE: BehaviorType = BehaviorType::Composite;
-    const NAME: &'static str = "Selector";
-    const ICON: &'static str = "❓";
-    const DESC: &'static str =
-        "A Selector will visit each child in order and succeed if any of its children \
-        succeed and not process any further children. It will process the first child, \
-        and if it fails will process the second, until a success is reached, at which \
-        point it will instantly return success. It will fail if all children fail.";
-}
-
-impl BehaviorUI for Selector {}
-
 pub fn run(
     mut commands: Commands,
     mut selectors: Query<
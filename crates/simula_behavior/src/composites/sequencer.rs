@@ -2,17 +2,29 @@ use crate::prelude::*;
 use bevy::prelude::*;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// A sequence will visit each child in order, starting with the first, and when that
 /// succeeds will call the second, and so on down the list of children. If any child
 /// fails it will immediately return failure to the parent. If the last child in the
 /// sequence succeeds, then the sequence will return success to its parent.
-#[derive(Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, BehaviorSpec, BehaviorUI,
+)]
+#[behavior(
+    composite,
+    icon = "➡",
+    desc = "A Sequence will visit each child in order, starting with the first, and when that \
+        succeeds will call the second, and so on down the list of children. If any child fails \
+        it will immediately return failure to the parent. If the last child in the sequence \
+        succeeds, then the sequence will return success to its parent."
+)]
 pub struct Sequencer {
     #[serde(default)]
     random: bool,
     #[serde(skip)]
     #[reflect(ignore)]
+    #[behavior(save)]
     pub seed: u64,
 }
 
@@ -25,19 +37,6 @@ impl Default for Sequencer {
     }
 }
 
-impl BehaviorSpec for Sequencer {
-    const TYPE: BehaviorType = BehaviorType::Composite;
-    const NAME: &'static str = "Sequencer";
-    const ICON: &'static str = "➡";
-    const DESC: &'static str =
-        "A Sequence will visit each child in order, starting with the first, and when that \
-        succeeds will call the second, and so on down the list of children. If any child \
-        fails it will immediately return failure to the parent. If the last child in the \
-        sequence succeeds, then the sequence will return success to its parent.";
-}
-
-impl BehaviorUI for Sequencer {}
-
 pub fn run(
     mut commands: Commands,
     mut sequences: Query<
@@ -0,0 +1,105 @@
+use crate::{prelude::*, random::shuffle_children};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use simula_core::prng::Prng;
+
+/// A shuffled sequencer works like a sequencer, but visits its children in a
+/// random order every time it starts. Children with a higher weight tend to be
+/// visited first, children without a weight have a weight of one.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    name = "Shuffled Sequencer",
+    icon = "🔀",
+    desc = "A Shuffled Sequencer will visit its children in a random order and fail if any of \
+        its children fail, like a Sequencer. Children with a higher weight tend to be visited \
+        first. It will succeed if all children succeed."
+)]
+pub struct ShuffledSequencer {
+    /// Weight of each child, in order
+    #[serde(default)]
+    #[behavior(property)]
+    pub weights: Vec<f32>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    order: Vec<Entity>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    rng: Option<Prng>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut sequences: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut ShuffledSequencer,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    seed: Res<BehaviorSeed>,
+) {
+    for (entity, children, mut sequence, node, started) in &mut sequences {
+        // Pick the order of the children when started
+        if started.is_some() || sequence.order.len() != children.len() {
+            let sequence = &mut *sequence;
+            let rng = sequence
+                .rng
+                .get_or_insert_with(|| seed.node_rng(node.tree, entity));
+            sequence.order = shuffle_children(rng, children, &sequence.weights);
+        }
+
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            let mut should_succeed = true;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            } in nodes.iter_many(sequence.order.iter())
+            {
+                if child_failure.is_some() {
+                    // Child failed, so we fail
+                    commands.entity(entity).insert(BehaviorFailure);
+                    should_succeed = false;
+                    break;
+                } else if child_success.is_some() {
+                    // Child succeeded, so we move to next child
+                } else {
+                    // Child is ready, pass on cursor
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                    should_succeed = false;
+                    break;
+                }
+            }
+            // If all children succeed, complete with success
+            if should_succeed {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use simula_core::prng::Prng;
+
+/// A utility selector scores its children every time it starts, and runs the
+/// child with the highest score, returning its result. Children are scored with
+/// a `Score` decorator, children without one have a score of zero. Children with
+/// a score of zero or less are never picked, it fails if no child can be picked.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    composite,
+    name = "Utility Selector",
+    icon = "🎯",
+    desc = "A Utility Selector scores its children every time it starts, and runs the child with \
+        the highest score, returning its result. Children are scored with a Score decorator. \
+        Children with a score of zero or less are never picked. It can pick at random weighted \
+        by score, and favor the last picked child."
+)]
+pub struct UtilitySelector {
+    /// Pick a child at random, weighted by its score
+    #[serde(default)]
+    #[behavior(property)]
+    pub random: bool,
+    /// Added to the score of the last picked child, so it is only replaced by a
+    /// child with a clearly better score
+    #[serde(default)]
+    #[behavior(property)]
+    pub hysteresis: f64,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    rng: Option<Prng>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    picked: Option<Entity>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    last: Option<Entity>,
+}
+
+// Score all children, `None` while a script is still busy
+fn score_children(
+    children: &BehaviorChildren,
+    scores: &mut Query<(&mut Score, &BehaviorNode)>,
+    scripts: &mut ScriptQueries,
+) -> Option<Result<Vec<(Entity, f64)>, String>> {
+    let mut child_scores = Vec::with_capacity(children.len());
+    for &child_entity in children.iter() {
+        let value = match scores.get_mut(child_entity) {
+            Ok((mut score, node)) => {
+                score.score.value = BehaviorPropValue::None;
+                match score.score.fetch(node, scripts)? {
+                    Ok(()) => match score.score.value {
+                        BehaviorPropValue::Some(value) => value,
+                        _ => 0.0,
+                    },
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            Err(_) => 0.0,
+        };
+        child_scores.push((child_entity, value));
+    }
+    Some(Ok(child_scores))
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut selectors: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut UtilitySelector,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut scores: Query<(&mut Score, &BehaviorNode)>,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+    seed: Res<BehaviorSeed>,
+) {
+    for (entity, children, mut selector, node, started) in &mut selectors {
+        if started.is_some() {
+            selector.picked = None;
+        }
+
+        // A child was picked, complete with its result
+        if let Some(picked) = selector.picked {
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(picked)
+            {
+                // Child failed, so we fail
+                if child_failure.is_some() {
+                    commands.entity(entity).insert(BehaviorFailure);
+                    selector.picked = None;
+                }
+                // Child succeeded, so we succeed
+                else if child_success.is_some() {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                    selector.picked = None;
+                }
+                // Child is ready, pass on cursor
+                else {
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                }
+            }
+            continue;
+        }
+
+        let child_scores = match score_children(children, &mut scores, &mut scripts) {
+            Some(Ok(child_scores)) => child_scores,
+            Some(Err(err)) => {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+            None => {
+                // Script is still busy
+                continue;
+            }
+        };
+
+        // Favor the last picked child
+        let candidates: Vec<(Entity, f64)> = child_scores
+            .into_iter()
+            .map(|(child_entity, score)| {
+                if selector.last == Some(child_entity) {
+                    (child_entity, score + selector.hysteresis)
+                } else {
+                    (child_entity, score)
+                }
+            })
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+
+        let picked = if selector.random && !candidates.is_empty() {
+            // Weighted random pick, deterministic for a given seed
+            let rng = selector
+                .rng
+                .get_or_insert_with(|| seed.node_rng(node.tree, entity));
+            let total: f64 = candidates.iter().map(|(_, score)| score).sum();
+            let mut roll = rng.rand_float() as f64 * total;
+            candidates
+                .iter()
+                .find(|(_, score)| {
+                    if roll < *score {
+                        true
+                    } else {
+                        roll -= score;
+                        false
+                    }
+                })
+                .or(candidates.last())
+                .map(|(child_entity, _)| *child_entity)
+        } else {
+            // Highest score, first child wins ties
+            candidates
+                .iter()
+                .fold(None, |best: Option<&(Entity, f64)>, candidate| match best {
+                    Some(best) if best.1 >= candidate.1 => Some(best),
+                    _ => Some(candidate),
+                })
+                .map(|(child_entity, _)| *child_entity)
+        };
+
+        match picked {
+            Some(child_entity) => {
+                selector.picked = Some(child_entity);
+                selector.last = Some(child_entity);
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+            None => {
+                // No child can be picked, so we fail
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use std::borrow::Cow;
+
+/// Time each shared cooldown key was last used, by `BehaviorClock`. It is not
+/// saved with the trees, the app saves it with `save_time` and restores it with
+/// `restore_time`, so times are rebased on the clock like node times.
+#[derive(Debug, Default, Clone, Resource, Deref, DerefMut, Serialize, Deserialize)]
+pub struct BehaviorCooldowns(pub HashMap<Cow<'static, str>, f64>);
+
+impl BehaviorSaveTime for BehaviorCooldowns {
+    fn save_time(&self, clock: &BehaviorClock) -> Self {
+        let times = self
+            .iter()
+            .map(|(key, time)| (key.clone(), time.save_time(clock)));
+        Self(times.collect())
+    }
+
+    fn restore_time(self, clock: &BehaviorClock) -> Self {
+        let times = self
+            .0
+            .into_iter()
+            .map(|(key, time)| (key, time.restore_time(clock)));
+        Self(times.collect())
+    }
+}
+
+/// Cooldown fails right away if its child completed less than `duration`
+/// seconds ago, otherwise it runs the child and returns the same result. Nodes
+/// with the same `key` share the cooldown, in any tree. Without a key, the
+/// cooldown is for this node only.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    decorator,
+    icon = "❄",
+    desc = "Cooldown fails right away if its child completed less than duration seconds ago, \
+        otherwise it runs the child and returns the same result. Nodes with the same key share \
+        the cooldown, in any tree."
+)]
+pub struct Cooldown {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub duration: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    #[behavior(prop)]
+    pub key: BehaviorPropStr,
+    #[serde(skip)]
+    #[behavior(save_time)]
+    pub last: Option<f64>,
+}
+
+pub fn run(
+    clock: Res<BehaviorClock>,
+    mut shared: ResMut<BehaviorCooldowns>,
+    mut commands: Commands,
+    mut cooldowns: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Cooldown,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut cooldown, node, started) in &mut cooldowns {
+        if children.len() != 1 {
+            error!("Decorator node requires one child");
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+
+        // Eval props again every time we start
+        if started.is_some() {
+            cooldown.duration.value = BehaviorPropValue::None;
+            cooldown.key.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = cooldown.duration.value {
+            let result = cooldown.duration.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = cooldown.key.value {
+            let result = cooldown.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        let (duration, key) = match (&cooldown.duration.value, &cooldown.key.value) {
+            (BehaviorPropValue::Some(duration), BehaviorPropValue::Some(key)) => {
+                (*duration, key.clone())
+            }
+            _ => continue, // Scripts are still busy
+        };
+
+        let elapsed = clock.elapsed_seconds_f64();
+        let child_entity = children[0]; // Safe because we checked for empty
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(child_entity)
+        {
+            if child_failure.is_some() || child_success.is_some() {
+                // Child completed, start the cooldown and return the same result
+                if key.is_empty() {
+                    cooldown.last = Some(elapsed);
+                } else {
+                    shared.insert(key, elapsed);
+                }
+                if child_failure.is_some() {
+                    commands.entity(entity).insert(BehaviorFailure);
+                } else {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+            } else {
+                let last = if key.is_empty() {
+                    cooldown.last
+                } else {
+                    shared.get(&key).copied()
+                };
+                match last {
+                    // Still cooling down, so we fail
+                    Some(last) if elapsed - last + f64::EPSILON < duration => {
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                    // Child is ready, pass on cursor
+                    _ => {
+                        commands.entity(entity).remove::<BehaviorCursor>();
+                        commands
+                            .entity(child_entity)
+                            .insert(BehaviorCursor::Delegate);
+                    }
+                }
+            }
+        }
+    }
+}
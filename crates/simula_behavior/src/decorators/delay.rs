@@ -1,62 +1,39 @@
-use crate::{prelude::*, property_ui_readonly};
+use crate::prelude::*;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// Delay will delay the execution of its child.
 #[derive(
-    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    InspectorOptions,
+    BehaviorSpec,
+    BehaviorUI,
 )]
+#[behavior(decorator, icon = "⌛", desc = "Delays the execution of its child")]
 #[reflect(InspectorOptions)]
 pub struct Delay {
     #[serde(default)]
+    #[behavior(prop)]
     pub duration: BehaviorPropGeneric<f64>,
     #[serde(skip)]
+    #[behavior(readonly, save_time)]
     pub start: f64,
     #[serde(skip)]
+    #[behavior(readonly, save)]
     pub ticks: u64,
 }
 
-impl BehaviorSpec for Delay {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Delay";
-    const ICON: &'static str = "⌛";
-    const DESC: &'static str = "Delays the execution of its child";
-}
-
-impl BehaviorUI for Delay {
-    fn ui(
-        &mut self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) -> bool {
-        let mut changed = false;
-        changed |= behavior_ui!(self, duration, state, ui, type_registry);
-        changed
-    }
-
-    fn ui_readonly(
-        &self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) {
-        behavior_ui_readonly!(self, duration, state, ui, type_registry);
-        match state {
-            Some(_) => {
-                property_ui_readonly!(self, start, state, ui, type_registry);
-                property_ui_readonly!(self, ticks, state, ui, type_registry);
-            }
-            _ => {}
-        }
-    }
-}
-
 pub fn run(
-    time: Res<Time>,
+    clock: Res<BehaviorClock>,
     mut commands: Commands,
     mut delays: Query<
         (
@@ -90,7 +67,7 @@ pub fn run(
                 continue;
             }
 
-            let elapsed = time.elapsed_seconds_f64();
+            let elapsed = clock.elapsed_seconds_f64();
             if started.is_some() {
                 delay.start = elapsed;
             }
@@ -1,15 +1,28 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// Guard evals a script to control the flow of execution. If the script returns
 /// `true`, the child is executed. If the script returns `false`, the child is
 /// not executed. The Scope of the script should be at the tree entity.
 #[derive(
-    Debug, Deref, DerefMut, Component, Reflect, FromReflect, Clone, Deserialize, Serialize,
+    Debug,
+    Deref,
+    DerefMut,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
 )]
+#[behavior(decorator, icon = "🚫")]
 pub struct Guard {
     #[serde(default)]
+    #[behavior(prop)]
     pub condition: BehaviorPropGeneric<bool>,
 }
 
@@ -24,40 +37,6 @@ impl Default for Guard {
     }
 }
 
-impl BehaviorSpec for Guard {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Guard";
-    const ICON: &'static str = "🚫";
-    const DESC: &'static str =
-        "Guard evals a script to control the flow of execution. If the script returns \
-        `true`, the child is executed. If the script returns `false`, the child is \
-        not executed. The Scope of the script should be at the tree entity.";
-}
-
-impl BehaviorUI for Guard {
-    fn ui(
-        &mut self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) -> bool {
-        let mut changed = false;
-        changed |= behavior_ui!(self, condition, state, ui, type_registry);
-        changed
-    }
-
-    fn ui_readonly(
-        &self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) {
-        behavior_ui_readonly!(self, condition, state, ui, type_registry);
-    }
-}
-
 pub fn run(
     mut commands: Commands,
     mut guards: Query<
@@ -1,20 +1,24 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// Identity decorator. It returns the same result as its child.
-#[derive(Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(decorator, icon = "=", desc = "Returns the same result as its child")]
 pub struct Identity;
 
-impl BehaviorSpec for Identity {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Identity";
-    const ICON: &'static str = "=";
-    const DESC: &'static str = "Returns the same result as its child";
-}
-
-impl BehaviorUI for Identity {}
-
 pub fn run(
     mut commands: Commands,
     mut identities: Query<(Entity, &BehaviorChildren), (With<Identity>, BehaviorRunQuery)>,
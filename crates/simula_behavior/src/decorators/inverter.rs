@@ -1,21 +1,24 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// Inverts result of their child node. Success becomes failure, and failure becomes success.
-#[derive(Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(decorator, icon = "~")]
 pub struct Inverter;
 
-impl BehaviorSpec for Inverter {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Inverter";
-    const ICON: &'static str = "~";
-    const DESC: &'static str = "Inverts result of their child node. Success becomes failure, \
-        and failure becomes success.";
-}
-
-impl BehaviorUI for Inverter {}
-
 pub fn run(
     mut commands: Commands,
     mut inverters: Query<(Entity, &BehaviorChildren), (With<Inverter>, BehaviorRunQuery)>,
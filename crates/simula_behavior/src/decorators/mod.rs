@@ -1,17 +1,23 @@
+pub mod cooldown;
 pub mod delay;
 pub mod guard;
 pub mod identity;
 pub mod inverter;
 pub mod repeater;
+pub mod retry;
+pub mod score;
 pub mod subtree;
 pub mod succeeder;
 pub mod timeout;
 
+pub use cooldown::{BehaviorCooldowns, Cooldown};
 pub use delay::Delay;
 pub use guard::Guard;
 pub use identity::Identity;
 pub use inverter::Inverter;
 pub use repeater::Repeater;
-pub use subtree::Subtree;
+pub use retry::Retry;
+pub use score::Score;
+pub use subtree::{Subtree, SubtreeParam, SubtreeScope};
 pub use succeeder::Succeeder;
 pub use timeout::Timeout;
@@ -1,6 +1,7 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 #[derive(Debug, Default, Reflect, FromReflect, Clone, Deserialize, Serialize)]
 pub enum Repeat {
@@ -12,22 +13,26 @@ pub enum Repeat {
 }
 
 /// Repeat a child until condition is met
-#[derive(Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(decorator, icon = "⟳")]
 pub struct Repeater {
     pub repeat: Repeat,
     #[serde(skip)]
+    #[behavior(save)]
     pub count: u64,
 }
 
-impl BehaviorSpec for Repeater {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Repeater";
-    const ICON: &'static str = "⟳";
-    const DESC: &'static str = "Repeat a child until condition is met";
-}
-
-impl BehaviorUI for Repeater {}
-
 pub fn run(
     mut commands: Commands,
     mut repeaters: Query<
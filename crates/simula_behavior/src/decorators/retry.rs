@@ -0,0 +1,147 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Retry runs its child again when it fails, up to a number of attempts. It
+/// succeeds as soon as the child succeeds, and fails if the last attempt fails.
+/// With a backoff, it waits before every retry, doubling the wait each time.
+#[derive(
+    Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, BehaviorSpec, BehaviorUI,
+)]
+#[behavior(
+    decorator,
+    icon = "↻",
+    desc = "Retry runs its child again when it fails, up to a number of attempts, three by \
+        default. Zero attempts retries forever. It succeeds as soon as the child succeeds. With \
+        a backoff, it waits before every retry, doubling the wait each time."
+)]
+pub struct Retry {
+    /// Number of attempts, three by default. Zero retries forever, so the node
+    /// never fails.
+    #[serde(default = "Retry::default_attempts")]
+    #[behavior(prop)]
+    pub attempts: BehaviorPropGeneric<u64>,
+    /// Seconds to wait before the first retry, doubled on every retry
+    #[serde(default)]
+    #[behavior(prop)]
+    pub backoff: BehaviorPropGeneric<f64>,
+    #[serde(skip)]
+    #[behavior(save)]
+    pub count: u64,
+    #[serde(skip)]
+    #[behavior(save_time)]
+    pub retry_at: f64,
+}
+
+impl Retry {
+    fn default_attempts() -> BehaviorPropGeneric<u64> {
+        BehaviorPropGeneric {
+            prop: BehaviorEval::Value(3),
+            ..default()
+        }
+    }
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            attempts: Self::default_attempts(),
+            backoff: default(),
+            count: 0,
+            retry_at: 0.0,
+        }
+    }
+}
+
+pub fn run(
+    clock: Res<BehaviorClock>,
+    mut commands: Commands,
+    mut retries: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Retry,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut retry, node, started) in &mut retries {
+        if children.len() != 1 {
+            error!("Decorator node requires one child");
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+
+        // Eval props again every time we start
+        if started.is_some() {
+            retry.count = 0;
+            retry.retry_at = 0.0;
+            retry.attempts.value = BehaviorPropValue::None;
+            retry.backoff.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = retry.attempts.value {
+            let result = retry.attempts.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = retry.backoff.value {
+            let result = retry.backoff.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        let (attempts, backoff) = match (&retry.attempts.value, &retry.backoff.value) {
+            (BehaviorPropValue::Some(attempts), BehaviorPropValue::Some(backoff)) => {
+                (*attempts, *backoff)
+            }
+            _ => continue, // Scripts are still busy
+        };
+
+        let elapsed = clock.elapsed_seconds_f64();
+        let child_entity = children[0]; // Safe because we checked for empty
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(child_entity)
+        {
+            // Child failed, retry if there are attempts left
+            if child_failure.is_some() {
+                if attempts == 0 || retry.count < attempts {
+                    let exponent = retry.count.saturating_sub(1).min(32) as i32;
+                    retry.retry_at = elapsed + backoff * 2f64.powi(exponent);
+                    commands.entity(entity).remove::<BehaviorRunning>();
+                } else {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+            // Child succeeded, so we succeed
+            else if child_success.is_some() {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            // Child is ready, pass on cursor once the backoff is over
+            else if elapsed + f64::EPSILON >= retry.retry_at {
+                retry.count += 1;
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+        }
+    }
+}
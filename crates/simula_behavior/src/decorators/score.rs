@@ -0,0 +1,70 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Score gives its child a score for a `UtilitySelector` to pick from. It
+/// returns the same result as its child.
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    decorator,
+    icon = "⚖",
+    desc = "Score gives its child a score for a Utility Selector to pick from. It returns \
+        the same result as its child."
+)]
+pub struct Score {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub score: BehaviorPropGeneric<f64>,
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut scores: Query<(Entity, &BehaviorChildren), (With<Score>, BehaviorRunQuery)>,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children) in &mut scores {
+        if children.len() != 1 {
+            error!("Decorator node requires one child");
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+
+        let child_entity = children[0]; // Safe because we checked for empty
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(child_entity)
+        {
+            // Child failed, so we fail
+            if child_failure.is_some() {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            // Child succeeded, so we succeed
+            else if child_success.is_some() {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            // Child is ready, pass on cursor
+            else {
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+        }
+    }
+}
@@ -1,17 +1,61 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+use simula_script::ScriptContext;
 use std::borrow::Cow;
 use std::fmt::Debug;
 
+/// Maps a blackboard key of the parent tree to a blackboard key of the subtree
+#[derive(Debug, Default, Reflect, FromReflect, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SubtreeParam {
+    pub parent: Cow<'static, str>,
+    pub child: Cow<'static, str>,
+}
+
+/// Blackboard used by a subtree
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SubtreeScope {
+    /// The subtree has its own blackboard
+    #[default]
+    Isolated,
+    /// The subtree uses the blackboard of the parent tree
+    Shared,
+}
+
 /// Subtree connects a behavior subtree to the current behavior tree.
-#[derive(Debug, Component, Reflect, FromReflect, Clone, Default, Deserialize, Serialize)]
+#[derive(
+    Debug,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Default,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+// A subtree, once connected works as a decorator node
+#[behavior(
+    subtree,
+    icon = "🏃",
+    desc = "Connects a behavior subtree to this node"
+)]
 pub struct Subtree<T: BehaviorFactory> {
     /// Behavior asset to load.
     pub asset: Cow<'static, str>,
     /// Unload the subtree when completed.
     #[serde(default)]
     pub unload: bool,
+    /// Parent values copied to the subtree when it starts.
+    #[serde(default)]
+    pub inputs: Vec<SubtreeParam>,
+    /// Subtree values copied to the parent when it completes.
+    #[serde(default)]
+    pub outputs: Vec<SubtreeParam>,
+    #[serde(default)]
+    pub scope: SubtreeScope,
     #[serde(skip)]
     #[reflect(ignore)]
     phantom: std::marker::PhantomData<T>,
@@ -23,19 +67,22 @@ impl<T: BehaviorFactory> Subtree<T> {
     }
 }
 
-impl<T> BehaviorSpec for Subtree<T>
-where
-    T: BehaviorFactory,
-{
-    // A subtree, once connected works as a decorator node
-    const TYPE: BehaviorType = BehaviorType::Subtree;
-    const NAME: &'static str = "Subtree";
-    const ICON: &'static str = "🏃";
-    const DESC: &'static str = "Connects a behavior subtree to this node";
+// Copy blackboard values from one tree to another, as `(from, to)` keys
+fn copy_params<'a>(
+    blackboards: &mut Blackboards,
+    from_tree: Entity,
+    to_tree: Entity,
+    params: impl Iterator<Item = (&'a str, &'a str)>,
+) -> Result<(), String> {
+    for (from, to) in params {
+        let value = blackboards
+            .get_value(from_tree, from)
+            .ok_or_else(|| format!("Blackboard key `{}` not found", from))?;
+        blackboards.set(to_tree, to, value)?;
+    }
+    Ok(())
 }
 
-impl<T> BehaviorUI for Subtree<T> where T: BehaviorFactory {}
-
 pub fn run<T: BehaviorFactory>(
     mut commands: Commands,
     mut subtrees: Query<
@@ -43,15 +90,36 @@ pub fn run<T: BehaviorFactory>(
             Entity,
             &BehaviorChildren,
             &Subtree<T>,
+            &BehaviorNode,
             Option<&BehaviorTree<T>>,
         ),
         BehaviorRunQuery,
     >,
     nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    ancestors: Query<(&Subtree<T>, &BehaviorNode)>,
+    script_ctxs: Query<&Handle<ScriptContext>>,
+    mut blackboards: Blackboards,
     asset_server: Res<AssetServer>,
 ) {
-    for (entity, children, subtree, child_tree) in &mut subtrees {
-        if child_tree.is_none() {
+    for (entity, children, subtree, node, child_tree) in &mut subtrees {
+        // Inlined subtrees have their child already, and run in the parent tree
+        if child_tree.is_none() && children.is_empty() {
+            // A subtree loading itself would load forever
+            let mut tree = node.tree;
+            let mut recursive = false;
+            while let Ok((ancestor, ancestor_node)) = ancestors.get(tree) {
+                if ancestor.asset == subtree.asset {
+                    recursive = true;
+                    break;
+                }
+                tree = ancestor_node.tree;
+            }
+            if recursive {
+                error!("Subtree asset `{}` references itself", subtree.asset);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+
             let behavior_document: Handle<BehaviorDocument> =
                 asset_server.load(subtree.asset.as_ref());
             commands
@@ -59,6 +127,12 @@ pub fn run<T: BehaviorFactory>(
                 .insert(behavior_document)
                 .insert(BehaviorTree::<T>::default())
                 .insert(BehaviorTreeReset::<T>::default());
+            // Shared subtrees reuse the parent script context, others get their own
+            if subtree.scope == SubtreeScope::Shared {
+                if let Ok(script_ctx) = script_ctxs.get(node.tree) {
+                    commands.entity(entity).insert(script_ctx.clone());
+                }
+            }
         } else if children.is_empty() {
             // Can be empty while loading subtree
         } else {
@@ -68,6 +142,11 @@ pub fn run<T: BehaviorFactory>(
                 continue;
             }
             let child_entity = children[0]; // Safe because we checked for empty
+            let subtree_tree = if child_tree.is_some() {
+                entity
+            } else {
+                node.tree
+            };
             if let Ok(BehaviorChildQueryItem {
                 child_entity,
                 child_parent: _,
@@ -85,18 +164,38 @@ pub fn run<T: BehaviorFactory>(
                 }
                 // Child succeeded, so we succeed
                 else if child_success.is_some() {
-                    commands.entity(entity).insert(BehaviorSuccess);
+                    let outputs = subtree
+                        .outputs
+                        .iter()
+                        .map(|param| (param.child.as_ref(), param.parent.as_ref()));
+                    let res = copy_params(&mut blackboards, subtree_tree, node.tree, outputs);
+                    if let Err(err) = res {
+                        error!("Subtree outputs failed: {}", err);
+                        commands.entity(entity).insert(BehaviorFailure);
+                    } else {
+                        commands.entity(entity).insert(BehaviorSuccess);
+                    }
                     done = true;
                 }
-                // Child is ready, pass on cursor
+                // Child is ready, pass on inputs and cursor
                 else {
+                    let inputs = subtree
+                        .inputs
+                        .iter()
+                        .map(|param| (param.parent.as_ref(), param.child.as_ref()));
+                    let res = copy_params(&mut blackboards, node.tree, subtree_tree, inputs);
+                    if let Err(err) = res {
+                        error!("Subtree inputs failed: {}", err);
+                        commands.entity(entity).insert(BehaviorFailure);
+                        continue;
+                    }
                     commands.entity(entity).remove::<BehaviorCursor>();
                     commands
                         .entity(child_entity)
                         .insert(BehaviorCursor::Delegate);
                 }
 
-                if done && subtree.unload {
+                if done && subtree.unload && child_tree.is_some() {
                     commands
                         .entity(entity)
                         .insert(BehaviorChildren::default())
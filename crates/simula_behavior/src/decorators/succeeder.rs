@@ -1,27 +1,27 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
 /// A succeeder will always return success, irrespective of what the child node
 /// actually returned. These are useful in cases where you want to process a branch
 /// of a tree where a failure is expected or anticipated, but you don’t want to
 /// abandon processing of a sequence that branch sits on.
-#[derive(Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(decorator, icon = "✓")]
 pub struct Succeeder;
 
-impl BehaviorSpec for Succeeder {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Succeeder";
-    const ICON: &'static str = "✓";
-    const DESC: &'static str =
-        "A succeeder will always return success, irrespective of what the child node \
-        actually returned. These are useful in cases where you want to process a branch \
-        of a tree where a failure is expected or anticipated, but you don’t want to \
-        abandon processing of a sequence that branch sits on.";
-}
-
-impl BehaviorUI for Succeeder {}
-
 pub fn run(
     mut commands: Commands,
     mut succeeders: Query<(Entity, &BehaviorChildren), (With<Succeeder>, BehaviorRunQuery)>,
@@ -1,60 +1,41 @@
-use crate::{prelude::*, property_ui_readonly};
+use crate::prelude::*;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use serde::{Deserialize, Serialize};
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
 
+/// Timeout will fail if its child does not return within the given time limit.
 #[derive(
-    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    InspectorOptions,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(
+    decorator,
+    icon = "🕓",
+    desc = "Fails if its child does not return within the given time limit"
 )]
 #[reflect(InspectorOptions)]
 pub struct Timeout {
     #[serde(default)]
+    #[behavior(prop)]
     pub duration: BehaviorPropGeneric<f64>,
     #[serde(skip)]
     #[reflect(ignore)]
+    #[behavior(readonly, save_time)]
     pub start: f64,
 }
 
-/// Timeout will fail if its child does not return within the given time limit.
-impl BehaviorSpec for Timeout {
-    const TYPE: BehaviorType = BehaviorType::Decorator;
-    const NAME: &'static str = "Timeout";
-    const ICON: &'static str = "🕓";
-    const DESC: &'static str = "Fails if its child does not return within the given time limit";
-}
-
-impl BehaviorUI for Timeout {
-    fn ui(
-        &mut self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) -> bool {
-        let mut changed = false;
-        changed |= behavior_ui!(self, duration, state, ui, type_registry);
-        changed
-    }
-
-    fn ui_readonly(
-        &self,
-        _label: Option<&str>,
-        state: Option<protocol::BehaviorState>,
-        ui: &mut bevy_inspector_egui::egui::Ui,
-        type_registry: &bevy::reflect::TypeRegistry,
-    ) {
-        behavior_ui_readonly!(self, duration, state, ui, type_registry);
-        match state {
-            Some(_) => {
-                property_ui_readonly!(self, start, state, ui, type_registry);
-            }
-            _ => {}
-        }
-    }
-}
-
 pub fn run(
-    time: Res<Time>,
+    clock: Res<BehaviorClock>,
     mut commands: Commands,
     mut timeouts: Query<
         (
@@ -87,7 +68,7 @@ pub fn run(
                 continue;
             }
 
-            let elapsed = time.elapsed_seconds_f64();
+            let elapsed = clock.elapsed_seconds_f64();
             if started.is_some() {
                 timeout.start = elapsed;
             }
@@ -37,6 +37,21 @@ where
         )
     }
 
+    fn add_property(
+        value: &mut dyn Reflect,
+        label: &str,
+        _state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let type_registry = type_registry.read();
+        ui.horizontal(|ui| {
+            ui.label(label);
+            bevy_inspector_egui::reflect_inspector::ui_for_value(value, ui, &type_registry)
+        })
+        .inner
+    }
+
     fn add_property_readonly(
         value: &dyn Reflect,
         label: &str,
@@ -72,6 +87,19 @@ macro_rules! behavior_ui_readonly {
     };
 }
 
+#[macro_export]
+macro_rules! property_ui {
+    ($s:expr, $field:ident, $state:expr, $ui:expr, $type_registry:expr) => {
+        Self::add_property(
+            $s.$field.as_reflect_mut(),
+            stringify!($field),
+            $state,
+            $ui,
+            $type_registry,
+        )
+    };
+}
+
 #[macro_export]
 macro_rules! property_ui_readonly {
     ($s:expr, $field:ident, $state:expr, $ui:expr, $type_registry:expr) => {
@@ -19,6 +19,8 @@ pub struct BehaviorNodeData<T: BehaviorFactory> {
     pub data: BehaviorData<T>,
     pub state: Option<BehaviorState>,
     pub entity: Option<RemoteEntity>,
+    /// Pause the behavior when the cursor reaches this node
+    pub breakpoint: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -77,6 +79,7 @@ pub enum BehaviorResponse<T: BehaviorFactory> {
     NameEdited(NodeId, String),
     NodeEditDone(NodeId),
     GoToSubtree(NodeId),
+    ToggleBreakpoint(NodeId),
 }
 
 /// The graph 'global' state. This state struct is passed around to the node and
@@ -141,6 +144,7 @@ where
                             BehaviorState::Running => egui::Color32::GREEN,
                             BehaviorState::Success => egui::Color32::DARK_GREEN,
                             BehaviorState::Failure => egui::Color32::DARK_RED,
+                            BehaviorState::Paused => egui::Color32::from_rgb(255, 140, 0),
                             _ => egui::Color32::from_rgb(80, 80, 80),
                         }
                     } else {
@@ -206,6 +210,7 @@ where
             },
             state: None,
             entity: None,
+            breakpoint: false,
         }
     }
 
@@ -528,6 +533,20 @@ where
         match &self.data {
             BehaviorData::Root => (),
             BehaviorData::Behavior(behavior) => {
+                let color = if self.breakpoint {
+                    egui::Color32::RED
+                } else {
+                    egui::Color32::DARK_GRAY
+                };
+                if ui
+                    .add(egui::Button::new(egui::RichText::new("●").color(color)).small())
+                    .on_hover_text("Toggle breakpoint")
+                    .clicked()
+                {
+                    responses.push(NodeResponse::User(BehaviorResponse::ToggleBreakpoint(
+                        node_id,
+                    )));
+                }
                 if behavior.typ() == BehaviorType::Subtree {
                     if ui.add(egui::Button::new("⤵").small()).clicked() {
                         responses.push(NodeResponse::User(BehaviorResponse::GoToSubtree(node_id)));
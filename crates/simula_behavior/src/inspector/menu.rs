@@ -2,13 +2,14 @@ use crate::{
     inspector::{utils, BehaviorInspector, BehaviorInspectorItem, BehaviorInspectorState},
     protocol::{
         BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient, StartOption,
-        StopOption,
+        StopOption, DEFAULT_TELEMETRY_RATE,
     },
     BehaviorFactory,
 };
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use simula_inspector::egui;
+use std::collections::BTreeMap;
 
 pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
     ui: &mut egui::Ui,
@@ -39,6 +40,11 @@ pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
                         start_option: StartOption::Spawn,
                         stop_option: StopOption::Despawn,
                         modified: true,
+                        diagnostics: None,
+                        telemetry_rate: DEFAULT_TELEMETRY_RATE,
+                        record: false,
+                        recording: None,
+                        replay_tick: 0,
                     },
                 );
                 behavior_inspector.selected = Some(file_id.clone());
@@ -89,31 +95,42 @@ pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
             .width(250.0)
             .selected_text(selected_label)
             .show_ui(ui, |ui| {
-                let mut selectable_behaviors: Vec<Option<BehaviorFileId>> = {
-                    let mut keys: Vec<BehaviorFileId> =
-                        behavior_inspector.behaviors.keys().cloned().collect();
-                    keys.sort_by(|a, b| {
-                        let name_a = &behavior_inspector.behaviors[a].name;
-                        let name_b = &behavior_inspector.behaviors[b].name;
-                        name_a.cmp(&name_b)
-                    });
-                    keys.iter().map(|key| Some(key.clone())).collect()
-                };
-                selectable_behaviors.insert(0, None);
-                for selectable_behavior in selectable_behaviors {
-                    let label =
-                        utils::get_label_from_file_id(&selectable_behavior, &behavior_inspector);
-                    let selectable_label = egui::SelectableLabel::new(
-                        behavior_inspector.selected == selectable_behavior,
-                        label,
-                    );
-                    if ui.add(selectable_label).clicked() {
-                        info!("Selected: {:?}", selectable_behavior);
-                        selected_behavior = selectable_behavior.clone();
-                        refresh_instances = true;
-                        refresh_orphans = true;
+                // group files by folder, folders and the files in them are sorted by name
+                let mut folders: BTreeMap<&str, Vec<(&str, &BehaviorFileId)>> = BTreeMap::new();
+                for (file_id, behavior_inspector_item) in &behavior_inspector.behaviors {
+                    let name: &str = &behavior_inspector_item.name;
+                    let (file_folder, file_stem) = name.rsplit_once('/').unwrap_or(("", name));
+                    folders
+                        .entry(file_folder)
+                        .or_default()
+                        .push((file_stem, file_id));
+                }
+
+                let mut clicked = None;
+                let selectable_label =
+                    egui::SelectableLabel::new(behavior_inspector.selected.is_none(), "None");
+                if ui.add(selectable_label).clicked() {
+                    clicked = Some(None);
+                }
+                for (file_folder, mut files) in folders {
+                    files.sort_by_key(|(file_stem, _)| *file_stem);
+                    ui.label(egui::RichText::new(format!("📁 {}", file_folder)).weak());
+                    for (file_stem, file_id) in files {
+                        let selectable_label = egui::SelectableLabel::new(
+                            behavior_inspector.selected.as_ref() == Some(file_id),
+                            format!("    {}", file_stem),
+                        );
+                        if ui.add(selectable_label).clicked() {
+                            clicked = Some(Some(file_id.clone()));
+                        }
                     }
                 }
+                if let Some(selectable_behavior) = clicked {
+                    info!("Selected: {:?}", selectable_behavior);
+                    selected_behavior = selectable_behavior;
+                    refresh_instances = true;
+                    refresh_orphans = true;
+                }
             });
         // update selected behavior
         let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
@@ -6,7 +6,11 @@ use crate::{
     protocol::{
         BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient,
         BehaviorProtocolServer, BehaviorServer, RemoteEntity, StartOption, StopOption,
+        DEFAULT_TELEMETRY_RATE,
     },
+    recorder::BehaviorRecording,
+    server::BehaviorServerConfig,
+    validate::{validate, Diagnostic},
     Behavior, BehaviorFactory,
 };
 pub use behavior::BehaviorUI;
@@ -15,7 +19,7 @@ use crossbeam_channel::unbounded;
 use egui_node_graph::NodeTemplateTrait;
 use serde::{Deserialize, Serialize};
 use simula_inspector::{egui, Inspector, Inspectors};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 mod behavior;
 pub mod graph;
@@ -33,23 +37,26 @@ where
     <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
 {
     fn build(&self, app: &mut App) {
-        // Setup bi-directional communication channels
-        let (protocol_client_sender, protocol_client_receiver) = unbounded();
-        let (protocol_server_sender, protocol_server_receiver) = unbounded();
-
-        let client = BehaviorClient::<T> {
-            sender: protocol_client_sender,
-            receiver: protocol_server_receiver,
-        };
-
-        let server = BehaviorServer::<T> {
-            sender: protocol_server_sender,
-            receiver: protocol_client_receiver,
-        };
-
-        app.insert_resource(client)
-            .insert_resource(server)
-            .insert_resource(BehaviorInspector::<T>::default())
+        // Setup bi-directional communication channels, unless the client
+        // is already connected to a remote server
+        if !app.world.contains_resource::<BehaviorClient<T>>() {
+            let (protocol_client_sender, protocol_client_receiver) = unbounded();
+            let (protocol_server_sender, protocol_server_receiver) = unbounded();
+
+            let client = BehaviorClient::<T> {
+                sender: protocol_client_sender,
+                receiver: protocol_server_receiver,
+            };
+
+            let server = BehaviorServer::<T> {
+                sender: protocol_server_sender,
+                receiver: protocol_client_receiver,
+            };
+
+            app.insert_resource(client).insert_resource(server);
+        }
+
+        app.insert_resource(BehaviorInspector::<T>::default())
             .add_startup_system(setup::<T>)
             .add_system(update::<T>);
     }
@@ -98,6 +105,16 @@ pub(self) struct BehaviorInspectorItem<T: BehaviorFactory> {
     pub start_option: StartOption,
     pub stop_option: StopOption,
     pub modified: bool,
+    /// Validation results, `None` when the behavior needs validating again
+    pub diagnostics: Option<Vec<Diagnostic>>,
+    /// Telemetry messages per second while running, 0 for every frame
+    pub telemetry_rate: f32,
+    /// Running behavior is recorded on every tick
+    pub record: bool,
+    /// Recording being replayed, telemetry is ignored while replaying
+    pub recording: Option<BehaviorRecording<T>>,
+    /// Index of the recorded tick shown in the graph
+    pub replay_tick: usize,
 }
 
 #[derive(Default, Clone, Resource)]
@@ -106,9 +123,19 @@ pub(self) struct BehaviorInspector<T: BehaviorFactory> {
     pub behaviors: HashMap<BehaviorFileId, BehaviorInspectorItem<T>>,
 }
 
+/// Asset folder subtree assets are validated in, the folder of the behavior
+/// server of this app, or the default one
+pub(self) fn asset_folder(config: Option<&BehaviorServerConfig>) -> PathBuf {
+    match config {
+        Some(config) => PathBuf::from(config.asset_folder.as_ref()),
+        None => PathBuf::from(BehaviorServerConfig::default().asset_folder.as_ref()),
+    }
+}
+
 fn setup<T>(mut inspectors: ResMut<Inspectors>)
 where
     T: BehaviorFactory + BehaviorInspectable + Serialize + for<'de> Deserialize<'de>,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
 {
     inspectors.inspectors.push(Inspector {
         menu_ui: menu::ui::<T>,
@@ -122,6 +149,7 @@ fn update<T>(
     type_registry: Res<AppTypeRegistry>,
     mut behavior_inspector: ResMut<BehaviorInspector<T>>,
     behavior_client: Res<BehaviorClient<T>>,
+    server_config: Option<Res<BehaviorServerConfig>>,
     mut graph_states: Query<&mut BehaviorGraphState>,
     mut editor_states: Query<&mut BehaviorEditorState<T>>,
 ) where
@@ -183,6 +211,7 @@ fn update<T>(
                     data: BehaviorData::Root,
                     state: None,
                     entity: None,
+                    breakpoint: false,
                 };
                 let root_node =
                     editor_state
@@ -282,6 +311,13 @@ fn update<T>(
                         if !error {
                             behavior_inspector_item.state =
                                 BehaviorInspectorState::Starting(elapsed);
+                            behavior_client
+                                .sender
+                                .send(BehaviorProtocolClient::Breakpoints(
+                                    file_id.clone(),
+                                    utils::graph_breakpoints(&editor_state.graph),
+                                ))
+                                .unwrap();
                             behavior_client
                                 .sender
                                 .send(BehaviorProtocolClient::Start(
@@ -363,10 +399,37 @@ fn update<T>(
                             start_option: StartOption::Spawn,
                             stop_option: StopOption::Despawn,
                             modified: false,
+                            diagnostics: None,
+                            telemetry_rate: DEFAULT_TELEMETRY_RATE,
+                            record: false,
+                            recording: None,
+                            replay_tick: 0,
                         },
                     );
                 }
             }
+            // Behavior file removed, keep it if there are unsaved changes
+            BehaviorProtocolServer::FileRemoved(file_id) => {
+                info!("Received FileRemoved: {:?}", file_id);
+                let modified = behavior_inspector
+                    .behaviors
+                    .get(&file_id)
+                    .map_or(false, |behavior_inspector_item| {
+                        behavior_inspector_item.modified
+                    });
+                if modified {
+                    continue;
+                }
+                if let Some(behavior_inspector_item) = behavior_inspector.behaviors.remove(&file_id)
+                {
+                    if let Some(entity) = behavior_inspector_item.entity {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+                if behavior_inspector.selected.as_ref() == Some(&file_id) {
+                    behavior_inspector.selected = None;
+                }
+            }
             // Receive instance running behavior
             BehaviorProtocolServer::Instances(file_id, remote_entities) => {
                 info!("Received Instances: {:?}", file_id);
@@ -402,6 +465,9 @@ fn update<T>(
 
                         behavior_inspector_item.behavior = Some(behavior.clone());
                         behavior_inspector_item.modified = false;
+                        let asset_folder = asset_folder(server_config.as_deref());
+                        behavior_inspector_item.diagnostics =
+                            Some(validate(&behavior, &asset_folder));
 
                         let mut graph_state = BehaviorGraphState {
                             type_registry: type_registry.0.clone(),
@@ -414,6 +480,7 @@ fn update<T>(
                             data: BehaviorData::Root,
                             state: None,
                             entity: None,
+                            breakpoint: false,
                         };
                         let root_node = editor_state.graph.add_node(
                             "Root".into(),
@@ -511,6 +578,8 @@ fn update<T>(
                     if let BehaviorInspectorState::Stopping(_) = behavior_inspector_item.state {
                         behavior_inspector_item.state = BehaviorInspectorState::Editing;
                     }
+                    behavior_inspector_item.record = false;
+                    behavior_inspector_item.recording = None;
                 } else {
                     error!("Unexpected behavior stopped: {:?}", file_id);
                 }
@@ -521,7 +590,10 @@ fn update<T>(
                 if let Some(behavior_inspector_item) =
                     behavior_inspector.behaviors.get_mut(&file_id)
                 {
-                    if let BehaviorInspectorState::Running = behavior_inspector_item.state {
+                    let replaying = behavior_inspector_item.recording.is_some();
+                    if let (BehaviorInspectorState::Running, false) =
+                        (&behavior_inspector_item.state, replaying)
+                    {
                         if let Some(entity) = behavior_inspector_item.entity {
                             if let Ok(mut editor_state) = editor_states.get_mut(entity) {
                                 if let Err(e) = utils::behavior_telemerty_to_graph(
@@ -538,6 +610,37 @@ fn update<T>(
                     error!("Unexpected behavior telemetry: {:?}", file_id);
                 }
             }
+            // Behavior recording, replay it from the last tick
+            BehaviorProtocolServer::Recording(file_id, recording) => {
+                info!(
+                    "Received Recording: {:?} ticks: {}",
+                    file_id,
+                    recording.ticks.len()
+                );
+                if let Some(behavior_inspector_item) =
+                    behavior_inspector.behaviors.get_mut(&file_id)
+                {
+                    let replay_tick = recording.ticks.len().saturating_sub(1);
+                    if let (Some(entity), Some(tick)) = (
+                        behavior_inspector_item.entity,
+                        recording.ticks.get(replay_tick),
+                    ) {
+                        if let Ok(mut editor_state) = editor_states.get_mut(entity) {
+                            if let Err(e) = utils::behavior_telemerty_to_graph(
+                                &mut editor_state.graph,
+                                None,
+                                &tick.telemetry,
+                            ) {
+                                error!("Failed to apply recording: {}", e);
+                            }
+                        }
+                    }
+                    behavior_inspector_item.replay_tick = replay_tick;
+                    behavior_inspector_item.recording = Some(recording);
+                } else {
+                    error!("Unexpected behavior recording: {:?}", file_id);
+                }
+            }
         }
     }
 }
@@ -160,6 +160,7 @@ pub fn behavior_into_graph<T>(
         data: behavior_data.clone(),
         state: None,
         entity: None,
+        breakpoint: false,
     };
     let node_id = editor
         .graph
@@ -236,13 +237,18 @@ where
         }
     };
 
-    // Update graph node with behavior telemetry
+    // Nothing changed in this subtree
+    let BehaviorTelemetry(Some(entity), state, behavior, _) = telemetry else {
+        return Ok(());
+    };
+
+    // Update graph node with behavior telemetry, data is only sent when changed
     let node: &mut egui_node_graph::Node<BehaviorNodeData<T>> = &mut graph.nodes[node_id];
-    if let BehaviorTelemetry(entity, state, Some(behavior), _) = telemetry {
+    if let Some(behavior) = behavior {
         node.user_data.data = BehaviorData::Behavior(behavior.clone());
-        node.user_data.state = Some(*state);
-        node.user_data.entity = entity.clone();
     }
+    node.user_data.state = Some(*state);
+    node.user_data.entity = Some(entity.clone());
 
     // Get node children
     let node_children: Vec<NodeId> = node
@@ -290,6 +296,57 @@ where
     Ok(())
 }
 
+// Connected children of a graph node, in output order
+fn get_node_children<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+) -> Vec<NodeId> {
+    graph.nodes[node_id]
+        .outputs
+        .iter()
+        .filter_map(|(_, output_id)| {
+            graph
+                .connections
+                .iter()
+                .find(|(input_id, rhs_output_id)| {
+                    output_id == *rhs_output_id
+                        && graph.inputs[*input_id].typ == BehaviorDataType::Flow
+                })
+                .and_then(|(input_id, _)| Some(graph.inputs[input_id].node))
+        })
+        .collect()
+}
+
+/// Nodes with a breakpoint, as paths of child indices from the root
+pub fn graph_breakpoints<T>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+) -> Vec<Vec<usize>>
+where
+    T: BehaviorFactory,
+{
+    fn collect<T: BehaviorFactory>(
+        graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+        node_id: NodeId,
+        path: &mut Vec<usize>,
+        breakpoints: &mut Vec<Vec<usize>>,
+    ) {
+        if graph.nodes[node_id].user_data.breakpoint {
+            breakpoints.push(path.clone());
+        }
+        for (index, child_id) in get_node_children(graph, node_id).into_iter().enumerate() {
+            path.push(index);
+            collect(graph, child_id, path, breakpoints);
+            path.pop();
+        }
+    }
+
+    let mut breakpoints = vec![];
+    if let Some(root_child_id) = get_root_child(graph) {
+        collect(graph, root_child_id, &mut vec![], &mut breakpoints);
+    }
+    breakpoints
+}
+
 pub fn layout_graph<T>(
     editor: &mut BehaviorEditorState<T>,
     node_id: Option<NodeId>,
@@ -1,34 +1,46 @@
 use crate::{
     inspector::{
+        asset_folder,
         graph::{
             BehaviorData, BehaviorDataType, BehaviorEditorState, BehaviorGraphState,
             BehaviorNodeTemplates, BehaviorResponse,
         },
         utils, BehaviorInspectable, BehaviorInspector, BehaviorInspectorState,
+        BehaviorNodeInspectable,
     },
-    protocol::{BehaviorFileName, StartOption, StopOption},
-    BehaviorFactory, BehaviorType,
+    protocol::{BehaviorClient, BehaviorFileName, BehaviorProtocolClient, StartOption, StopOption},
+    recorder::{recording_path, BehaviorRecording},
+    server::BehaviorServerConfig,
+    validate::validate,
+    BehaviorFactory, BehaviorResume, BehaviorType,
 };
 use bevy::{prelude::*, window::PrimaryWindow};
 use egui_node_graph::NodeResponse;
+use serde::{Deserialize, Serialize};
 use simula_inspector::egui;
 
-pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
-    context: &mut egui::Context,
-    world: &mut World,
-) {
+pub fn ui<T>(context: &mut egui::Context, world: &mut World)
+where
+    T: BehaviorFactory + BehaviorInspectable + Serialize + for<'de> Deserialize<'de>,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
     let elapsed = world.get_resource::<Time>().unwrap().elapsed();
 
     let selected_behavior = world
         .resource_mut::<BehaviorInspector<T>>()
         .selected
         .clone();
-    let Some(selected_behavior) = selected_behavior else { return;};
+    let Some(selected_behavior) = selected_behavior else {
+        return;
+    };
     let behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
-    let Some((file_name, inspector_item_state, entity))
-        = behavior_inspector.behaviors
+    let Some((file_name, inspector_item_state, entity)) = behavior_inspector
+        .behaviors
         .get(&selected_behavior)
-        .and_then(|item| Some((item.name.clone(), item.state.clone(), item.entity))) else { return;};
+        .and_then(|item| Some((item.name.clone(), item.state.clone(), item.entity)))
+    else {
+        return;
+    };
 
     match inspector_item_state {
         BehaviorInspectorState::Editing => {}
@@ -41,7 +53,15 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         BehaviorInspectorState::Stopping(_) => {}
         _ => return,
     }
-    let Some(entity) = entity else { return;};
+    let Some(entity) = entity else {
+        return;
+    };
+
+    let replaying = world
+        .resource::<BehaviorInspector<T>>()
+        .behaviors
+        .get(&selected_behavior)
+        .map_or(false, |item| item.recording.is_some());
 
     let mut behavior_graphs = world.query::<(
         Entity,
@@ -54,7 +74,7 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         editor_state.editing = false;
         match inspector_item_state {
             BehaviorInspectorState::Editing => {
-                editor_state.editing = true;
+                editor_state.editing = !replaying;
             }
             BehaviorInspectorState::Save => {}
             BehaviorInspectorState::Saving(_) => {}
@@ -67,12 +87,41 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         }
     }
 
+    // Validate the behavior again when it changes
+    let needs_validation = world
+        .resource::<BehaviorInspector<T>>()
+        .behaviors
+        .get(&selected_behavior)
+        .map_or(false, |item| item.diagnostics.is_none());
+    if needs_validation {
+        let asset_folder = asset_folder(world.get_resource::<BehaviorServerConfig>());
+        let diagnostics = behavior_graphs
+            .get(world, entity)
+            .ok()
+            .and_then(|(_, _, _, editor_state)| utils::graph_to_behavior(editor_state, None).ok())
+            .map(|behavior| validate(&behavior, &asset_folder))
+            .unwrap_or_default();
+        if let Some(item) = world
+            .resource_mut::<BehaviorInspector<T>>()
+            .behaviors
+            .get_mut(&selected_behavior)
+        {
+            item.diagnostics = Some(diagnostics);
+        }
+    }
+
     let window = world
         .query_filtered::<&Window, With<PrimaryWindow>>()
         .single(world);
     let default_size = egui::vec2(window.width() * 0.7, window.height() * 0.7);
 
     let mut reset_graph_layout = false;
+    // request to send once the window is done
+    let mut request = None;
+    // recorded tick to show in the graph
+    let mut replay_tick = None;
+    // behavior to restore in the graph, after replaying while editing
+    let mut restore_behavior = None;
 
     let mut open = true;
     let mut window_name = format!("{}", *file_name);
@@ -107,6 +156,10 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                 // if this behavior item has been modified in any way
                 // e.g. the graph has been edited, or renamed
                 let mut modified = behavior_inspector_item.modified;
+                // if the graph has been edited, and needs validating again
+                let mut revalidate = false;
+                // a recording can be replayed if none is being replayed
+                let replay_enabled = behavior_inspector_item.recording.is_none();
 
                 ui.horizontal(|ui| {
                     egui::menu::bar(ui, |ui| {
@@ -204,6 +257,28 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                         }
                                     }
                                 });
+                            // replay a saved recording, unless there are unsaved changes
+                            let path = recording_path(&behavior_inspector_item.name);
+                            if ui
+                                .add_enabled(
+                                    replay_enabled && !modified && path.is_file(),
+                                    egui::Button::new("📼"),
+                                )
+                                .on_hover_text("Replay saved recording")
+                                .clicked()
+                            {
+                                match BehaviorRecording::<T>::load(&path) {
+                                    Ok(recording) => {
+                                        let tick = recording.ticks.len().saturating_sub(1);
+                                        behavior_inspector_item.replay_tick = tick;
+                                        behavior_inspector_item.recording = Some(recording);
+                                        replay_tick = Some(tick);
+                                    }
+                                    Err(err) => {
+                                        error!("Failed to load recording: {}", err);
+                                    }
+                                }
+                            }
                         }
 
                         if let BehaviorInspectorState::Running = inspector_item_state {
@@ -240,6 +315,58 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                         }
                                     }
                                 });
+                            if ui
+                                .add(
+                                    egui::DragValue::new(
+                                        &mut behavior_inspector_item.telemetry_rate,
+                                    )
+                                    .clamp_range(0.0..=120.0)
+                                    .suffix(" Hz"),
+                                )
+                                .on_hover_text("Telemetry rate, 0 for every frame")
+                                .changed()
+                            {
+                                request = Some(BehaviorProtocolClient::TelemetryRate(
+                                    selected_behavior.clone(),
+                                    behavior_inspector_item.telemetry_rate,
+                                ));
+                            }
+                            if ui
+                                .selectable_label(behavior_inspector_item.record, "⏺")
+                                .on_hover_text("Record every tick")
+                                .clicked()
+                            {
+                                behavior_inspector_item.record = !behavior_inspector_item.record;
+                                request = Some(BehaviorProtocolClient::Record(
+                                    selected_behavior.clone(),
+                                    behavior_inspector_item.record,
+                                ));
+                            }
+                            if ui
+                                .add_enabled(
+                                    replay_enabled && behavior_inspector_item.record,
+                                    egui::Button::new("⏪"),
+                                )
+                                .on_hover_text("Replay recording")
+                                .clicked()
+                            {
+                                request = Some(BehaviorProtocolClient::Recording(
+                                    selected_behavior.clone(),
+                                ));
+                            }
+                            let resumes = [
+                                ("⏯", "Continue", BehaviorResume::Continue),
+                                ("↷", "Step over", BehaviorResume::StepOver),
+                                ("↘", "Step into", BehaviorResume::StepInto),
+                            ];
+                            for (icon, hover, resume) in resumes {
+                                if ui.button(icon).on_hover_text(hover).clicked() {
+                                    request = Some(BehaviorProtocolClient::Resume(
+                                        selected_behavior.clone(),
+                                        resume,
+                                    ));
+                                }
+                            }
                         }
 
                         ui.style_mut().visuals.extreme_bg_color =
@@ -266,7 +393,75 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                     });
                 });
 
+                // Scrub through the recording being replayed
+                let mut save_recording = false;
+                let mut close_replay = false;
+                if let Some(recording) = &behavior_inspector_item.recording {
+                    let tick_index = &mut behavior_inspector_item.replay_tick;
+                    ui.horizontal(|ui| {
+                        let last_tick = recording.ticks.len().saturating_sub(1);
+                        if ui
+                            .add(egui::Slider::new(tick_index, 0..=last_tick).show_value(false))
+                            .changed()
+                        {
+                            replay_tick = Some(*tick_index);
+                        }
+                        if let Some(tick) = recording.ticks.get(*tick_index) {
+                            let blackboard = tick
+                                .blackboard
+                                .0
+                                .iter()
+                                .map(|(key, value)| format!("{} = {:?}", key, value))
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            ui.label(format!("tick {} at {:.2}s", tick.tick, tick.elapsed))
+                                .on_hover_text(blackboard);
+                        }
+                        if ui.button("💾").on_hover_text("Save recording").clicked() {
+                            save_recording = true;
+                        }
+                        if ui.button("✖").on_hover_text("Stop replaying").clicked() {
+                            close_replay = true;
+                        }
+                    });
+                }
+                if save_recording {
+                    if let Some(recording) = &behavior_inspector_item.recording {
+                        let path = recording_path(&behavior_inspector_item.name);
+                        match recording.save(&path) {
+                            Ok(()) => info!("Saved recording: {}", path.display()),
+                            Err(err) => error!("Failed to save recording: {}", err),
+                        }
+                    }
+                }
+                if close_replay {
+                    behavior_inspector_item.recording = None;
+                    if let BehaviorInspectorState::Running = inspector_item_state {
+                        // Get complete telemetry again
+                        request = Some(BehaviorProtocolClient::TelemetryRate(
+                            selected_behavior.clone(),
+                            behavior_inspector_item.telemetry_rate,
+                        ));
+                    } else {
+                        restore_behavior = behavior_inspector_item.behavior.clone();
+                    }
+                }
+
                 if !behavior_inspector_item.collapsed {
+                    if let Some(diagnostics) = &behavior_inspector_item.diagnostics {
+                        for diagnostic in diagnostics {
+                            let (icon, color) = if diagnostic.is_error() {
+                                ("⛔", egui::Color32::LIGHT_RED)
+                            } else {
+                                ("⚠", egui::Color32::YELLOW)
+                            };
+                            ui.colored_label(
+                                color,
+                                format!("{} {}: {}", icon, diagnostic.name, diagnostic.message),
+                            );
+                        }
+                    }
+
                     egui::Frame::none()
                         .fill(egui::Color32::from_rgba_unmultiplied(42, 40, 45, 140))
                         .stroke(egui::Stroke::NONE)
@@ -340,12 +535,14 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                 match response {
                                     NodeResponse::CreatedNode(_) => {
                                         modified = true;
+                                        revalidate = true;
                                     }
                                     NodeResponse::DeleteNodeFull {
                                         node_id: _node_id,
                                         node: _node,
                                     } => {
                                         modified = true;
+                                        revalidate = true;
                                     }
                                     NodeResponse::SelectNode(node_id) => {
                                         graph_state.active_node = Some(node_id);
@@ -358,6 +555,7 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                         input: input_id,
                                     } => {
                                         modified = true;
+                                        revalidate = true;
 
                                         // Check if output is already connected, and if so, remove the previous connection
                                         let mut removes = vec![];
@@ -423,12 +621,14 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                         input: _input,
                                     } => {
                                         modified = true;
+                                        revalidate = true;
                                     }
                                     NodeResponse::User(BehaviorResponse::NodeEdited(
                                         node_id,
                                         data,
                                     )) => {
                                         modified = true;
+                                        revalidate = true;
                                         if let Some(node) =
                                             editor_state.graph.nodes.get_mut(node_id)
                                         {
@@ -440,12 +640,26 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                                         name,
                                     )) => {
                                         modified = true;
+                                        revalidate = true;
                                         if let Some(node) =
                                             editor_state.graph.nodes.get_mut(node_id)
                                         {
                                             node.label = name;
                                         }
                                     }
+                                    NodeResponse::User(BehaviorResponse::ToggleBreakpoint(
+                                        node_id,
+                                    )) => {
+                                        if let Some(node) =
+                                            editor_state.graph.nodes.get_mut(node_id)
+                                        {
+                                            node.user_data.breakpoint = !node.user_data.breakpoint;
+                                        }
+                                        request = Some(BehaviorProtocolClient::Breakpoints(
+                                            selected_behavior.clone(),
+                                            utils::graph_breakpoints(&editor_state.graph),
+                                        ));
+                                    }
                                     _ => {}
                                 }
                             }
@@ -458,6 +672,9 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
                     .get_mut(&selected_behavior)
                     .unwrap();
                 behavior_inspector_item.modified = modified;
+                if revalidate {
+                    behavior_inspector_item.diagnostics = None;
+                }
             });
         });
 
@@ -468,6 +685,38 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         }
     }
 
+    if let Some(request) = request {
+        if let Some(behavior_client) = world.get_resource::<BehaviorClient<T>>() {
+            behavior_client.sender.send(request).unwrap();
+        }
+    }
+
+    if let Some(replay_tick) = replay_tick {
+        let telemetry = world
+            .resource::<BehaviorInspector<T>>()
+            .behaviors
+            .get(&selected_behavior)
+            .and_then(|item| item.recording.as_ref())
+            .and_then(|recording| recording.ticks.get(replay_tick))
+            .map(|tick| tick.telemetry.clone());
+        if let (Some(telemetry), Ok((_, _, _, mut editor_state))) =
+            (telemetry, behavior_graphs.get_mut(world, entity))
+        {
+            let graph = &mut editor_state.graph;
+            if let Err(e) = utils::behavior_telemerty_to_graph(graph, None, &telemetry) {
+                error!("Failed to apply recording: {}", e);
+            }
+        }
+    }
+
+    if let Some(behavior) = restore_behavior {
+        if let Ok((_, _, _, mut editor_state)) = behavior_graphs.get_mut(world, entity) {
+            if let Err(e) = utils::behavior_to_graph(&mut editor_state, None, &behavior) {
+                error!("Failed to restore behavior: {}", e);
+            }
+        }
+    }
+
     if !open {
         let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
         behavior_inspector.selected = None;
@@ -1,7 +1,8 @@
 use actions::*;
 use asset::{
-    behavior_document_to_asset, behavior_tree_reset, Behavior, BehaviorAsset, BehaviorAssetLoader,
-    BehaviorDocument,
+    behavior_asset_reload, behavior_document_reload, behavior_document_to_asset,
+    behavior_tree_reset, behavior_tree_restore, Behavior, BehaviorAsset, BehaviorAssetLoader,
+    BehaviorDocument, BehaviorHotReload, BehaviorSubtrees,
 };
 use bevy::{
     ecs::{
@@ -13,26 +14,46 @@ use bevy::{
     prelude::*,
     reflect::{TypeRegistry, TypeUuid},
 };
+use blackboard::{
+    blackboard_changes, Blackboard, BlackboardChanged, BlackboardSchema, BlackboardValue,
+};
 use composites::*;
 use decorators::*;
+use save::{behavior_tree_load, BehaviorNodeState};
 use serde::{Deserialize, Serialize};
+use signal::SignalScope;
 use simula_script::{ScriptContext, ScriptPlugin};
 use strum::AsRefStr;
+use tick::{init_behavior_schedule, BehaviorAppExt, BehaviorClock, BehaviorPostTickSchedule};
 
 pub mod actions;
 pub mod asset;
+pub mod blackboard;
 pub mod composites;
 pub mod decorators;
 pub mod inspector;
 pub mod property;
 pub mod protocol;
+pub mod random;
+pub mod recorder;
+pub mod remote;
+pub mod save;
 pub mod server;
+pub mod signal;
 pub mod test;
+pub mod tick;
+pub mod validate;
 
 pub mod prelude {
     pub use crate::actions::*;
     pub use crate::asset::{
-        Behavior, BehaviorAsset, BehaviorAssetLoader, BehaviorDocument, BehaviorTreeReset,
+        Behavior, BehaviorAsset, BehaviorAssetLoader, BehaviorDocument, BehaviorFile,
+        BehaviorHotReload, BehaviorInlineError, BehaviorSubtrees, BehaviorTreeReset,
+        BehaviorTreeSource,
+    };
+    pub use crate::blackboard::{
+        Blackboard, BlackboardChanged, BlackboardKey, BlackboardSchema, BlackboardSnapshot,
+        BlackboardValue, Blackboards,
     };
     pub use crate::composites::*;
     pub use crate::decorators::*;
@@ -44,15 +65,29 @@ pub mod prelude {
         BehaviorPropStr, BehaviorPropValue, ScriptQueries,
     };
     pub use crate::protocol::{self};
+    pub use crate::random::BehaviorSeed;
+    pub use crate::recorder::{BehaviorRecordedTick, BehaviorRecorder, BehaviorRecording};
+    pub use crate::remote::{BehaviorRemoteClientPlugin, BehaviorRemoteServerPlugin};
+    pub use crate::save::{
+        BehaviorNodeSave, BehaviorNodeState, BehaviorSaveField, BehaviorSaveTime, BehaviorTreeSave,
+    };
     pub use crate::server::{
-        AssetTracker, BehaviorServerPlugin, BehaviorTracker, BehaviorTrackers, EntityTracker,
+        AssetTracker, BehaviorServerConfig, BehaviorServerPlugin, BehaviorTracker,
+        BehaviorTrackers, EntityTracker, TelemetryTracker,
+    };
+    pub use crate::signal::{BehaviorSignal, SignalScope};
+    pub use crate::tick::{
+        BehaviorAppExt, BehaviorBaseSet, BehaviorClock, BehaviorPostTickSchedule, BehaviorSchedule,
+        BehaviorTickMode, BehaviorTiming, BehaviorTraversal,
     };
+    pub use crate::validate::{validate, Diagnostic, DiagnosticKind, Severity};
     pub use crate::{behavior_ui, behavior_ui_readonly};
     pub use crate::{
-        BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem, BehaviorChildren,
-        BehaviorCursor, BehaviorFactory, BehaviorFailure, BehaviorIdleQuery, BehaviorMissing,
-        BehaviorNode, BehaviorParent, BehaviorPlugin, BehaviorRunQuery, BehaviorRunning,
-        BehaviorSet, BehaviorSpec, BehaviorStarted, BehaviorSuccess, BehaviorTree,
+        BehaviorBreakpoint, BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem,
+        BehaviorChildren, BehaviorCursor, BehaviorDespawn, BehaviorFactory, BehaviorFailure,
+        BehaviorIdleQuery, BehaviorMissing, BehaviorNode, BehaviorParent, BehaviorPaused,
+        BehaviorPlugin, BehaviorResume, BehaviorRunQuery, BehaviorRunning, BehaviorSet,
+        BehaviorSpec, BehaviorStarted, BehaviorStopped, BehaviorSuccess, BehaviorTree,
         BehaviorTreePlugin, BehaviorType,
     };
 }
@@ -64,46 +99,94 @@ impl Plugin for BehaviorPlugin {
         app.add_plugin(ScriptPlugin)
             .init_asset_loader::<BehaviorAssetLoader>()
             .add_asset::<BehaviorDocument>()
-            .configure_set(BehaviorSet::PostUpdate.in_base_set(CoreSet::PostUpdate))
-            .add_systems(
-                (clear_behavior_started, complete_behavior, start_behavior)
-                    .chain()
-                    .in_set(BehaviorSet::PostUpdate),
-            )
+            .init_resource::<BehaviorHotReload>()
+            .init_resource::<BehaviorSubtrees>()
+            .init_resource::<BehaviorSeed>()
+            .init_resource::<BehaviorCooldowns>()
+            .add_event::<BlackboardChanged>()
             .register_type::<BehaviorNode>()
             .register_type::<BehaviorSuccess>()
             .register_type::<BehaviorRunning>()
             .register_type::<BehaviorFailure>()
+            .register_type::<BehaviorStopped>()
+            .register_type::<BehaviorDespawn>()
             .register_type::<BehaviorCursor>()
+            .register_type::<BehaviorPaused>()
+            .register_type::<BehaviorBreakpoint>()
+            .register_type::<BehaviorResume>()
             .register_type::<BehaviorParent>()
             .register_type::<BehaviorChildren>()
             .register_type::<BehaviorType>()
+            .register_type::<Blackboard>()
+            .register_type::<BlackboardValue>()
+            .register_type::<BlackboardSchema>()
             .register_type::<Debug>()
             .register_type::<Selector>()
             .register_type::<Sequencer>()
             .register_type::<All>()
             .register_type::<Any>()
+            .register_type::<Parallel>()
+            .register_type::<ParallelPolicy>()
+            .register_type::<ReactiveSelector>()
+            .register_type::<ReactiveSequencer>()
+            .register_type::<UtilitySelector>()
+            .register_type::<RandomSelector>()
+            .register_type::<ShuffledSequencer>()
+            .register_type::<BehaviorSeed>()
             .register_type::<Repeater>()
             .register_type::<Inverter>()
             .register_type::<Succeeder>()
             .register_type::<Wait>()
+            .register_type::<SetBlackboard>()
+            .register_type::<IncrementBlackboard>()
+            .register_type::<CompareBlackboard>()
+            .register_type::<WaitForEvent>()
+            .register_type::<EmitSignal>()
+            .register_type::<SignalScope>()
+            .register_type::<CompareOp>()
             .register_type::<Delay>()
             .register_type::<Identity>()
             .register_type::<Guard>()
             .register_type::<Timeout>()
-            .add_system(debug::run)
-            .add_system(selector::run)
-            .add_system(sequencer::run)
-            .add_system(all::run)
-            .add_system(any::run)
-            .add_system(repeater::run)
-            .add_system(inverter::run)
-            .add_system(succeeder::run)
-            .add_system(wait::run)
-            .add_system(delay::run)
-            .add_system(identity::run)
-            .add_system(guard::run)
-            .add_system(timeout::run);
+            .register_type::<Score>()
+            .register_type::<Retry>()
+            .register_type::<Cooldown>()
+            .register_type::<SubtreeParam>()
+            .register_type::<SubtreeScope>();
+
+        // Behavior nodes run in the behavior schedule, once per tick
+        init_behavior_schedule(app);
+        app.add_behavior_system(debug::run)
+            .add_behavior_system(selector::run)
+            .add_behavior_system(sequencer::run)
+            .add_behavior_system(all::run)
+            .add_behavior_system(any::run)
+            .add_behavior_system(parallel::run)
+            .add_behavior_system(reactive_selector::run)
+            .add_behavior_system(reactive_sequencer::run)
+            .add_behavior_system(utility_selector::run)
+            .add_behavior_system(random_selector::run)
+            .add_behavior_system(shuffled_sequencer::run)
+            .add_behavior_system(repeater::run)
+            .add_behavior_system(inverter::run)
+            .add_behavior_system(succeeder::run)
+            .add_behavior_system(wait::run)
+            .add_behavior_system(set_blackboard::run)
+            .add_behavior_system(increment_blackboard::run)
+            .add_behavior_system(compare_blackboard::run)
+            // Signals are seen on the next hand-off, no matter the order nodes run
+            .add_behavior_system(wait_for_event::run.before(emit_signal::run))
+            .add_behavior_system(emit_signal::run)
+            .add_behavior_system(delay::run)
+            .add_behavior_system(identity::run)
+            .add_behavior_system(guard::run)
+            .add_behavior_system(timeout::run)
+            .add_behavior_system(score::run)
+            .add_behavior_system(retry::run)
+            .add_behavior_system(cooldown::run)
+            .add_behavior_system(async_action::poll_tasks)
+            .add_behavior_system(async_action::cancel_tasks)
+            .add_behavior_system(blackboard_changes.in_set(BehaviorSet::PostUpdate));
     }
 }
 
@@ -122,7 +205,19 @@ where
     fn build(&self, app: &mut App) {
         app.register_type::<BehaviorTree<T>>()
             .add_asset::<BehaviorAsset<T>>()
-            .add_systems((behavior_document_to_asset::<T>, behavior_tree_reset::<T>).chain());
+            .add_systems(
+                (
+                    behavior_document_reload::<T>,
+                    behavior_asset_reload::<T>,
+                    behavior_document_to_asset::<T>,
+                    behavior_tree_reset::<T>,
+                    apply_system_buffers,
+                    behavior_tree_restore::<T>,
+                    behavior_tree_load::<T>,
+                )
+                    .chain(),
+            )
+            .add_system(recorder::record::<T>.in_schedule(BehaviorPostTickSchedule));
     }
 }
 
@@ -166,6 +261,9 @@ pub trait BehaviorFactory:
     /// get behavior type: composite, decorator, action
     fn typ(&self) -> BehaviorType;
 
+    /// get scripts of behavior props, checked by `validate`
+    fn scripts(&self) -> Vec<&str>;
+
     /// get behavior properties for inspector
     fn inner_reflect(&self) -> &dyn Reflect;
 
@@ -191,12 +289,34 @@ pub trait BehaviorFactory:
     /// copy behavior data from entity into this behavior
     fn copy_from(&mut self, _entity: Entity, _world: &World) -> Result<(), BehaviorMissing>;
 
+    /// compare behavior data of the entity with this behavior, like
+    /// `Reflect::reflect_partial_eq`, without copying it
+    fn compare_from(&self, entity: Entity, world: &World) -> Result<Option<bool>, BehaviorMissing> {
+        let mut data = self.clone();
+        data.copy_from(entity, world)?;
+        Ok(self.reflect_partial_eq(&data))
+    }
+
+    /// get runtime state of this behavior, to save a running tree
+    fn save_state(&self, _clock: &BehaviorClock) -> Option<BehaviorNodeState> {
+        None
+    }
+
+    /// restore runtime state of this behavior, saved with `save_state`
+    fn restore_state(
+        &mut self,
+        _state: &BehaviorNodeState,
+        _clock: &BehaviorClock,
+        _entity_map: &EntityMap,
+    ) {
+    }
+
     /// list all behaviors, with an instance of each
     fn list() -> Vec<Self>;
 }
 
 /// A marker added to currently running behaviors
-#[derive(Default, Debug, Reflect, Clone, Copy, Component)]
+#[derive(Default, Debug, Reflect, Clone, Copy, Component, Serialize, Deserialize)]
 #[reflect(Component)]
 #[component(storage = "SparseSet")]
 pub enum BehaviorCursor {
@@ -213,12 +333,44 @@ pub enum BehaviorCursor {
 #[component(storage = "SparseSet")]
 pub struct BehaviorRunning;
 
-/// A marker added to entities that want to run a behavior
+/// A marker added to a paused behavior tree, and to its running nodes. The
+/// `BehaviorClock` keeps running while a tree is paused, nodes that measure
+/// time see the time spent paused when the tree resumes.
 #[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
 #[reflect(Component)]
 #[component(storage = "SparseSet")]
 pub struct BehaviorPaused;
 
+/// A marker added to nodes that pause their tree when the cursor reaches them
+#[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct BehaviorBreakpoint;
+
+/// Insert into a paused behavior tree to resume it
+#[derive(
+    Debug, Default, Reflect, FromReflect, Clone, Copy, Component, PartialEq, Serialize, Deserialize,
+)]
+#[reflect(Component)]
+pub enum BehaviorResume {
+    /// Run until the next breakpoint
+    #[default]
+    Continue,
+    /// Pause at the next node started that is not a child of the paused node
+    StepOver,
+    /// Pause at the next node started
+    StepInto,
+}
+
+/// Where a stepping behavior tree pauses next
+#[derive(Debug, Clone, Copy, Component)]
+enum BehaviorStep {
+    /// At the next node started that is not a child of this node
+    Over(Entity),
+    /// At the next node started
+    Into,
+}
+
 /// A marker added to entities that want to run a behavior
 #[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
 #[reflect(Component)]
@@ -237,12 +389,23 @@ pub struct BehaviorSuccess;
 #[component(storage = "SparseSet")]
 pub struct BehaviorFailure;
 
-/// A marker added to behaviors that are stopped without completing
+/// A marker added for one tick to behaviors that are stopped without completing,
+/// because their parent completed or aborted them. Nodes that hold resources,
+/// like animations or reservations, release them in a system that queries
+/// their component `With<BehaviorStopped>`.
 #[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
 #[reflect(Component)]
 #[component(storage = "SparseSet")]
 pub struct BehaviorStopped;
 
+/// A marker added to behavior entities removed while their nodes run. They are
+/// despawned with their children at the end of the tick, once the stopped
+/// nodes have seen `BehaviorStopped`.
+#[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct BehaviorDespawn;
+
 /// A marker added to behavior node entities
 #[derive(Component, Debug, Eq, PartialEq, Reflect)]
 #[reflect(Component, MapEntities, PartialEq)]
@@ -316,6 +479,29 @@ where
     fn insert_with(commands: &mut EntityCommands, data: &Self) {
         commands.insert(data.clone());
     }
+
+    /// Scripts evaluated by the behavior props, checked by `validate`
+    fn scripts(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    /// Runtime fields that are not part of the behavior document, like the
+    /// start time of a wait, saved with a running tree. Times are saved with
+    /// `save::save_time`, relative to `clock`.
+    fn save_state(&self, _clock: &BehaviorClock) -> Option<BehaviorNodeState> {
+        None
+    }
+
+    /// Restore runtime fields saved with `save_state`. Saved times are rebased
+    /// on `clock` with `save::restore_time`, saved entities are mapped to the
+    /// restored nodes with `entity_map`.
+    fn restore_state(
+        &mut self,
+        _state: &BehaviorNodeState,
+        _clock: &BehaviorClock,
+        _entity_map: &EntityMap,
+    ) {
+    }
 }
 
 /// A component added to identify the root of a behavior tree
@@ -330,9 +516,10 @@ where
     /// Create a script context to be used by the behavior tree
     pub fn create_script_context() -> ScriptContext {
         let mut scope = ScriptContext::new();
+        blackboard::register_script_types(&mut scope.engine);
         let mut blackboard = simula_script::script::Map::new();
         blackboard.insert("state".into(), 0.into());
-        scope.scope.push("blackboard", blackboard);
+        scope.scope.push(blackboard::BLACKBOARD, blackboard);
         scope
     }
 
@@ -443,13 +630,31 @@ impl BehaviorTrace {
     }
 }
 
-/// Clear BehaviorStarted every frame
-fn clear_behavior_started(mut commands: Commands, started: Query<Entity, With<BehaviorStarted>>) {
+/// Clear BehaviorStarted every frame, paused nodes keep it until they run
+fn clear_behavior_started(
+    mut commands: Commands,
+    started: Query<Entity, (With<BehaviorStarted>, Without<BehaviorPaused>)>,
+) {
     for entity in &mut started.iter() {
         commands.entity(entity).remove::<BehaviorStarted>();
     }
 }
 
+/// Clear BehaviorStopped, nodes see it for one tick
+fn clear_behavior_stopped(mut commands: Commands, stopped: Query<Entity, With<BehaviorStopped>>) {
+    for entity in &stopped {
+        commands.entity(entity).remove::<BehaviorStopped>();
+    }
+}
+
+/// Despawn behavior entities marked with `BehaviorDespawn`, after their stopped
+/// nodes had a tick to see `BehaviorStopped`
+fn despawn_behavior(mut commands: Commands, despawned: Query<Entity, With<BehaviorDespawn>>) {
+    for entity in &despawned {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 /// Process completed behaviors, pass cursor to parent
 fn complete_behavior(
     mut commands: Commands,
@@ -470,8 +675,10 @@ fn complete_behavior(
         Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
     >,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut clock: ResMut<BehaviorClock>,
 ) {
     for (entity, success, failure, parent, children, name) in &dones {
+        clock.handoff();
         let state = if success.is_some() {
             "SUCCESS"
         } else if failure.is_some() {
@@ -509,7 +716,16 @@ fn complete_behavior(
 /// Process ready behaviors, start them
 fn start_behavior(
     mut commands: Commands,
-    ready: Query<(Entity, Option<&BehaviorChildren>, &Name, &BehaviorCursor), BehaviorReadyQuery>,
+    ready: Query<
+        (
+            Entity,
+            Option<&BehaviorChildren>,
+            &Name,
+            &BehaviorCursor,
+            &BehaviorNode,
+        ),
+        BehaviorReadyQuery,
+    >,
     nodes: Query<
         (Entity, Option<&BehaviorChildren>),
         Or<(
@@ -519,9 +735,17 @@ fn start_behavior(
             With<BehaviorFailure>,
         )>,
     >,
+    active: Query<(Entity, &BehaviorNode), Or<(With<BehaviorCursor>, With<BehaviorRunning>)>>,
+    breakpoints: Query<(), With<BehaviorBreakpoint>>,
+    paused: Query<(), With<BehaviorPaused>>,
+    steps: Query<&BehaviorStep>,
+    parents: Query<&BehaviorParent>,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut clock: ResMut<BehaviorClock>,
 ) {
-    for (entity, children, name, cursor) in &ready {
+    let mut paused_trees = vec![];
+    for (entity, children, name, cursor, node) in &ready {
+        clock.handoff();
         // Reset all children recursively
         if let Some(children) = children {
             reset_children(&mut commands, children, &nodes);
@@ -546,11 +770,177 @@ fn start_behavior(
         if starting {
             commands.entity(entity).insert(BehaviorStarted);
         }
+
+        // Nodes started in a paused tree are paused too
+        if paused.contains(node.tree) || paused_trees.contains(&node.tree) {
+            commands.entity(entity).insert(BehaviorPaused);
+            continue;
+        }
+
+        // Pause the tree at breakpoints, or at the next step
+        let pause = starting
+            && (breakpoints.contains(entity)
+                || match steps.get(node.tree) {
+                    Ok(BehaviorStep::Into) => true,
+                    Ok(BehaviorStep::Over(over)) => !is_descendant(entity, *over, &parents),
+                    Err(_) => false,
+                });
+        if pause {
+            debug!(
+                "[{}] PAUSED {}",
+                entity.index().to_string(),
+                name.to_string()
+            );
+            if let Some(trace) = trace.as_mut() {
+                trace.push(format!("[{}] PAUSED {}", entity.index(), name));
+            }
+            commands
+                .entity(node.tree)
+                .insert(BehaviorPaused)
+                .remove::<BehaviorStep>();
+            commands.entity(entity).insert(BehaviorPaused);
+            for (active_entity, active_node) in &active {
+                if active_node.tree == node.tree {
+                    commands.entity(active_entity).insert(BehaviorPaused);
+                }
+            }
+            paused_trees.push(node.tree);
+        }
+    }
+}
+
+/// Check if `entity` is a child of `ancestor`, at any depth
+fn is_descendant(entity: Entity, ancestor: Entity, parents: &Query<&BehaviorParent>) -> bool {
+    let mut entity = entity;
+    while let Ok(parent) = parents.get(entity) {
+        if **parent == ancestor {
+            return true;
+        }
+        entity = **parent;
     }
+    false
 }
 
-/// Stop all children nodes recursively, but keep their execution states
-fn stop_children(
+/// Resume paused behavior trees with a `BehaviorResume`, and set where they pause next
+fn resume_behavior(
+    mut commands: Commands,
+    resumed: Query<(Entity, &BehaviorResume)>,
+    paused: Query<(Entity, &BehaviorNode, Option<&BehaviorCursor>), With<BehaviorPaused>>,
+) {
+    for (tree, resume) in &resumed {
+        commands
+            .entity(tree)
+            .remove::<BehaviorResume>()
+            .remove::<BehaviorPaused>();
+
+        // Paused node holding the cursor, where the tree is stepping from
+        let mut cursor = None;
+        for (entity, node, node_cursor) in &paused {
+            if node.tree == tree {
+                commands.entity(entity).remove::<BehaviorPaused>();
+                if node_cursor.is_some() {
+                    cursor = Some(entity);
+                }
+            }
+        }
+
+        match (resume, cursor) {
+            (BehaviorResume::Continue, _) => {
+                commands.entity(tree).remove::<BehaviorStep>();
+            }
+            (BehaviorResume::StepOver, Some(cursor)) => {
+                commands.entity(tree).insert(BehaviorStep::Over(cursor));
+            }
+            (BehaviorResume::StepOver, None) | (BehaviorResume::StepInto, _) => {
+                commands.entity(tree).insert(BehaviorStep::Into);
+            }
+        }
+    }
+}
+
+/// Abort a running behavior node, and stop all its children recursively
+pub(crate) fn abort_behavior(
+    commands: &mut Commands,
+    entity: Entity,
+    name: &Name,
+    nodes: &Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+    trace: &mut Option<ResMut<BehaviorTrace>>,
+) {
+    debug!(
+        "[{}] ABORTED {}",
+        entity.index().to_string(),
+        name.to_string()
+    );
+    if let Some(trace) = trace.as_mut() {
+        trace.push(format!("[{}] ABORTED {}", entity.index(), name));
+    }
+    commands.entity(entity).remove::<BehaviorCursor>();
+    commands.entity(entity).remove::<BehaviorRunning>();
+    commands.entity(entity).insert(BehaviorStopped);
+    if let Ok((_, Some(children))) = nodes.get(entity) {
+        stop_children(commands, children, nodes);
+    }
+}
+
+/// Re-evaluate the `Guard` children before the running child of a reactive
+/// composite, on a copy of their condition. The running child is aborted at the
+/// first guard that evaluates to `abort_on`, a guard whose script errors
+/// evaluates to `false`. Returns that guard.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn recheck_guards(
+    commands: &mut Commands,
+    children: &BehaviorChildren,
+    abort_on: bool,
+    nodes: &Query<BehaviorChildQuery, With<BehaviorNode>>,
+    names: &Query<&Name, With<BehaviorNode>>,
+    guards: &Query<(&Guard, &BehaviorNode)>,
+    stoppables: &Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+    scripts: &mut property::ScriptQueries,
+    trace: &mut Option<ResMut<BehaviorTrace>>,
+) -> Option<Entity> {
+    // A child is running, find it
+    let running = nodes.iter_many(children.iter()).position(|child| {
+        child.child_running.is_some()
+            && child.child_success.is_none()
+            && child.child_failure.is_none()
+    })?;
+    for &guard_entity in children.iter().take(running) {
+        let Ok((guard, node)) = guards.get(guard_entity) else {
+            continue;
+        };
+        let mut condition = guard.condition.clone();
+        condition.value = property::BehaviorPropValue::None;
+        let passed = match condition.fetch(node, scripts) {
+            Some(Err(err)) => {
+                error!("Script errored: {:?}", err);
+                Some(false)
+            }
+            _ => match condition.value {
+                property::BehaviorPropValue::Some(passed) => Some(passed),
+                // Script is still busy
+                _ => None,
+            },
+        };
+        if passed == Some(abort_on) {
+            let running_entity = children[running];
+            if let Ok(name) = names.get(running_entity) {
+                abort_behavior(commands, running_entity, name, stoppables, trace);
+            }
+            return Some(guard_entity);
+        }
+    }
+    None
+}
+
+/// Stop all running children nodes recursively, but keep their execution states.
+/// Stopped nodes get `BehaviorStopped`.
+pub(crate) fn stop_children(
     commands: &mut Commands,
     children: &BehaviorChildren,
     nodes: &Query<
@@ -561,12 +951,29 @@ fn stop_children(
     for (entity, children) in nodes.iter_many(children.iter()) {
         commands.entity(entity).remove::<BehaviorCursor>();
         commands.entity(entity).remove::<BehaviorRunning>();
+        commands.entity(entity).insert(BehaviorStopped);
         if let Some(children) = children {
             stop_children(commands, children, nodes);
         }
     }
 }
 
+/// Stop the running nodes from `root` down, like `stop_children`, and mark
+/// `entity` to be despawned with its children once they have seen
+/// `BehaviorStopped`. `entity` is `root` or one of its ancestors.
+pub fn stop_and_despawn(
+    commands: &mut Commands,
+    entity: Entity,
+    root: Entity,
+    nodes: &Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
+) {
+    stop_children(commands, &BehaviorChildren(vec![root]), nodes);
+    commands.entity(entity).insert(BehaviorDespawn);
+}
+
 /// Reset all children nodes recursively and remove their execution states
 fn reset_children(
     commands: &mut Commands,
@@ -44,6 +44,14 @@ where
     fn value(&self) -> &BehaviorPropValue<Self::ValueType>;
     fn value_mut(&mut self) -> &mut BehaviorPropValue<Self::ValueType>;
 
+    /// Script evaluated by the prop, `None` for a value
+    fn script(&self) -> Option<&str> {
+        match self.prop() {
+            BehaviorEval::Eval { eval, handle: _ } => Some(eval.as_ref()),
+            BehaviorEval::Value(_) => None,
+        }
+    }
+
     fn fetch(
         &mut self,
         node: &BehaviorNode,
@@ -117,6 +125,10 @@ where
     <<Prop as BehaviorProp>::ValueType as TryFrom<<Prop as BehaviorProp>::ScriptType>>::Error:
         std::fmt::Debug,
 {
+    /// Script evaluated by the prop, `None` for a value or no prop
+    pub fn script(&self) -> Option<&str> {
+        self.0.as_ref().and_then(BehaviorProp::script)
+    }
 }
 
 #[derive(Debug, Reflect, FromReflect, Clone, Deserialize, Serialize, Default)]
@@ -226,6 +238,19 @@ pub struct ScriptQueries<'w, 's> {
     ctxs: ResMut<'w, Assets<ScriptContext>>,
 }
 
+impl<'w, 's> ScriptQueries<'w, 's> {
+    /// Get the script context of a behavior tree
+    pub fn script_ctx_mut(&mut self, tree: Entity) -> Result<&mut ScriptContext, String> {
+        let script_ctx_handle = self
+            .ctx_handles
+            .get(tree)
+            .map_err(|_| "Cannot find script context handle in tree entity".to_string())?;
+        self.ctxs
+            .get_mut(script_ctx_handle)
+            .ok_or_else(|| "Invalid script context handle".to_string())
+    }
+}
+
 fn make_handle(
     eval: impl Into<Cow<'static, str>>,
     node: &BehaviorNode,
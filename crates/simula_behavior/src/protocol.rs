@@ -1,9 +1,12 @@
-use crate::{Behavior, BehaviorFactory};
+use crate::{recorder::BehaviorRecording, Behavior, BehaviorFactory, BehaviorResume};
 use bevy::{prelude::*, utils::Uuid};
 use crossbeam_channel::{Receiver, Sender};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
+/// Telemetry messages sent per second for a behavior file, unless changed by the client
+pub const DEFAULT_TELEMETRY_RATE: f32 = 30.0;
+
 #[derive(Resource)]
 pub struct BehaviorClient<T: BehaviorFactory> {
     pub sender: Sender<BehaviorProtocolClient<T>>,
@@ -35,14 +38,14 @@ impl RemoteEntity {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StartOption {
     Spawn,
     Attach(RemoteEntity),
     Insert(RemoteEntity),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StopOption {
     Despawn,
     Detach,
@@ -73,8 +76,10 @@ impl BehaviorFileId {
 )]
 pub struct BehaviorFileName(pub Cow<'static, str>);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BehaviorProtocolClient<T: BehaviorFactory> {
+    /// Client connected, files are listed again and complete telemetry is sent next
+    Connected,
     /// Request instances running behavior
     Instances(BehaviorFileId),
     /// Request orphans, instances that have behavior trees but no behavior
@@ -92,11 +97,26 @@ pub enum BehaviorProtocolClient<T: BehaviorFactory> {
     ),
     /// Request behavior to be stopped
     Stop(BehaviorFileId, StopOption),
+    /// Request telemetry to be sent this many times per second, 0 for every frame.
+    /// Complete telemetry is sent next.
+    TelemetryRate(BehaviorFileId, f32),
+    /// Request the running behavior to be recorded on every tick, or not. Behaviors
+    /// started by the server are not recorded until requested.
+    Record(BehaviorFileId, bool),
+    /// Request recorded ticks of the running behavior
+    Recording(BehaviorFileId),
+    /// Request breakpoints on nodes, each node is a path of child indices from the root
+    Breakpoints(BehaviorFileId, Vec<Vec<usize>>),
+    /// Request paused behavior to be resumed
+    Resume(BehaviorFileId, BehaviorResume),
 }
 
+#[derive(Serialize, Deserialize)]
 pub enum BehaviorProtocolServer<T: BehaviorFactory> {
     /// Behavior file listed
     FileName(BehaviorFileId, BehaviorFileName),
+    /// Behavior file removed from disk
+    FileRemoved(BehaviorFileId),
     /// Instances running behavior
     Instances(BehaviorFileId, Vec<RemoteEntity>),
     /// Instances that have behavior trees but no behavior
@@ -111,9 +131,14 @@ pub enum BehaviorProtocolServer<T: BehaviorFactory> {
     Stopped(BehaviorFileId),
     /// Behavior telemetry
     Telemetry(BehaviorFileId, BehaviorTelemetry<T>),
+    /// Recorded ticks of the running behavior
+    Recording(BehaviorFileId, BehaviorRecording<T>),
 }
 
-#[derive(Debug, Default)]
+/// Telemetry of a behavior node and its children. Only changes since the last
+/// telemetry are sent: data is `None` when it didn't change, and a node is
+/// `BehaviorTelemetry::default()` when nothing changed in its subtree.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BehaviorTelemetry<T: BehaviorFactory>(
     pub Option<RemoteEntity>,
     pub BehaviorState,
@@ -121,7 +146,7 @@ pub struct BehaviorTelemetry<T: BehaviorFactory>(
     pub Vec<BehaviorTelemetry<T>>,
 );
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BehaviorState {
     #[default]
     None,
@@ -129,4 +154,5 @@ pub enum BehaviorState {
     Cursor,
     Success,
     Failure,
+    Paused,
 }
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use simula_core::prng::Prng;
+
+/// Global seed of random behavior nodes. Each node mixes it with its tree and
+/// node entities, so trees vary from each other but the same seed and entities
+/// give the same choices. Random by default, set it for reproducible runs.
+#[derive(Debug, Clone, Copy, Resource, Reflect, FromReflect, Deref)]
+#[reflect(Resource)]
+pub struct BehaviorSeed(pub u64);
+
+impl Default for BehaviorSeed {
+    fn default() -> Self {
+        Self(rand::random())
+    }
+}
+
+impl BehaviorSeed {
+    /// Random generator of a node
+    pub fn node_rng(&self, tree: Entity, node: Entity) -> Prng {
+        let mut hash = self.0;
+        for value in [tree.to_bits(), node.to_bits()] {
+            hash = (hash ^ value).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            hash ^= hash >> 32;
+        }
+        Prng::new(hash)
+    }
+}
+
+/// Random order of children, children with a higher weight tend to come first.
+/// Children without a weight have a weight of one, children with a weight of
+/// zero or less come last.
+pub fn shuffle_children(rng: &mut Prng, children: &[Entity], weights: &[f32]) -> Vec<Entity> {
+    let weight = |index: usize| weights.get(index).copied().unwrap_or(1.0).max(0.0);
+    let mut remaining: Vec<usize> = (0..children.len()).collect();
+    let mut order = Vec::with_capacity(children.len());
+    while !remaining.is_empty() {
+        let total: f32 = remaining.iter().map(|&index| weight(index)).sum();
+        let pick = if total > 0.0 {
+            let mut roll = rng.rand_float_range(0.0, total);
+            remaining
+                .iter()
+                .position(|&index| {
+                    if roll < weight(index) {
+                        true
+                    } else {
+                        roll -= weight(index);
+                        false
+                    }
+                })
+                .or_else(|| remaining.iter().rposition(|&index| weight(index) > 0.0))
+                .unwrap_or(0)
+        } else {
+            rng.rand_u32() as usize % remaining.len()
+        };
+        order.push(children[remaining.remove(pick)]);
+    }
+    order
+}
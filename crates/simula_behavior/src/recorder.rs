@@ -0,0 +1,170 @@
+use crate::{
+    asset::{Behavior, BehaviorAsset},
+    blackboard::BlackboardSnapshot,
+    protocol::BehaviorTelemetry,
+    server::build_telemetry,
+    tick::BehaviorClock,
+    BehaviorFactory,
+};
+use bevy::{prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use simula_script::ScriptContext;
+use std::{collections::VecDeque, path::Path, path::PathBuf};
+
+/// Number of ticks a recorder keeps by default
+pub const DEFAULT_RECORDER_CAPACITY: usize = 600;
+
+/// Folder recordings are saved in
+pub const RECORDING_FOLDER: &str = "recordings";
+
+/// State of a behavior tree at the end of a tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorRecordedTick<T: BehaviorFactory> {
+    /// Tick of the behavior clock
+    pub tick: u64,
+    /// Time of the behavior clock, in seconds
+    pub elapsed: f64,
+    /// Complete telemetry of the tree
+    pub telemetry: BehaviorTelemetry<T>,
+    pub blackboard: BlackboardSnapshot,
+}
+
+/// Recorded ticks of a behavior tree, oldest first
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BehaviorRecording<T: BehaviorFactory> {
+    /// Behavior the ticks were recorded from
+    pub behavior: Option<Behavior<T>>,
+    pub ticks: VecDeque<BehaviorRecordedTick<T>>,
+}
+
+impl<T> BehaviorRecording<T>
+where
+    T: BehaviorFactory + Serialize + DeserializeOwned,
+{
+    /// Save the recording as RON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        if let Some(dir) = path.as_ref().parent() {
+            std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        std::fs::write(path, data).map_err(|err| err.to_string())
+    }
+
+    /// Load a recording saved with `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        ron::from_str(&data).map_err(|err| err.to_string())
+    }
+}
+
+/// Where the recording of a behavior file is saved, e.g. `bht/u/agent` is saved
+/// as `recordings/bht_u_agent.bhr.ron`
+pub fn recording_path(file_name: &str) -> PathBuf {
+    let file_name = file_name.replace(['/', '\\'], "_");
+    Path::new(RECORDING_FOLDER).join(format!("{}.bhr.ron", file_name))
+}
+
+/// Add to a behavior tree to record its state on every tick. Only the last
+/// `capacity` ticks are kept.
+#[derive(Component)]
+pub struct BehaviorRecorder<T: BehaviorFactory> {
+    pub capacity: usize,
+    pub recording: BehaviorRecording<T>,
+}
+
+impl<T: BehaviorFactory> Default for BehaviorRecorder<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_RECORDER_CAPACITY)
+    }
+}
+
+impl<T: BehaviorFactory> BehaviorRecorder<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recording: BehaviorRecording::default(),
+        }
+    }
+
+    // Add a tick, replacing the last one if recorded in the same tick
+    fn push(&mut self, tick: BehaviorRecordedTick<T>) {
+        let ticks = &mut self.recording.ticks;
+        if ticks.back().map_or(false, |last| last.tick == tick.tick) {
+            ticks.pop_back();
+        }
+        ticks.push_back(tick);
+        while ticks.len() > self.capacity.max(1) {
+            ticks.pop_front();
+        }
+    }
+}
+
+/// Record behavior trees with a `BehaviorRecorder`. Runs once per tick in
+/// `BehaviorPostTickSchedule`, after the cursor hand-offs.
+pub fn record<T: BehaviorFactory>(world: &mut World) {
+    let clock = world.resource::<BehaviorClock>();
+    let (tick, elapsed) = (clock.ticks(), clock.elapsed_seconds_f64());
+
+    let mut trees = world.query::<(
+        Entity,
+        &BehaviorRecorder<T>,
+        &Handle<BehaviorAsset<T>>,
+        &Children,
+        Option<&Handle<ScriptContext>>,
+    )>();
+
+    let world_ref: &World = world;
+    let behavior_assets = world_ref.resource::<Assets<BehaviorAsset<T>>>();
+    let script_ctxs = world_ref.get_resource::<Assets<ScriptContext>>();
+    let mut recorded = vec![];
+    for (entity, recorder, behavior_asset, children, script_ctx) in trees.iter(world_ref) {
+        let Some(behavior_asset) = behavior_assets.get(behavior_asset) else {
+            continue;
+        };
+        let Some(root) = children.first() else {
+            continue;
+        };
+        let mut telemetry = BehaviorTelemetry::default();
+        let result = build_telemetry(
+            world_ref,
+            *root,
+            &mut telemetry,
+            &behavior_asset.behavior,
+            &mut HashMap::default(),
+            &mut HashMap::default(),
+        );
+        if result.is_err() {
+            continue;
+        }
+        let blackboard = script_ctx
+            .and_then(|script_ctx| script_ctxs?.get(script_ctx))
+            .map(BlackboardSnapshot::from_script_context)
+            .unwrap_or_default();
+        // Only clone the behavior the first time
+        let behavior = recorder
+            .recording
+            .behavior
+            .is_none()
+            .then(|| behavior_asset.behavior.clone());
+        recorded.push((
+            entity,
+            behavior,
+            BehaviorRecordedTick {
+                tick,
+                elapsed,
+                telemetry,
+                blackboard,
+            },
+        ));
+    }
+
+    for (entity, behavior, tick) in recorded {
+        if let Some(mut recorder) = world.get_mut::<BehaviorRecorder<T>>(entity) {
+            if behavior.is_some() {
+                recorder.recording.behavior = behavior;
+            }
+            recorder.push(tick);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    protocol::{BehaviorClient, BehaviorProtocolClient, BehaviorProtocolServer, BehaviorServer},
+    BehaviorFactory,
+};
+use bevy::prelude::*;
+use crossbeam_channel::{select, unbounded, Receiver, Sender, TryRecvError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+/// How long a client waits before connecting again
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// How long the server waits before checking for clients again
+const ACCEPT_DELAY: Duration = Duration::from_millis(100);
+
+/// Serve the behavior protocol of this app over TCP, so an inspector running in
+/// another process can attach to it. Add it instead of a local inspector, before
+/// `BehaviorServerPlugin`. If the address can't be bound the error is logged and
+/// the behavior server is disabled.
+pub struct BehaviorRemoteServerPlugin<T: BehaviorFactory> {
+    pub addr: Cow<'static, str>,
+    pub phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: BehaviorFactory> BehaviorRemoteServerPlugin<T> {
+    pub fn new(addr: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            addr: addr.into(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<T> Plugin for BehaviorRemoteServerPlugin<T>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    fn build(&self, app: &mut App) {
+        match listen::<T>(self.addr.as_ref()) {
+            Ok((server, addr)) => {
+                info!("Serving behavior protocol on {}", addr);
+                app.insert_resource(server);
+            }
+            Err(err) => {
+                error!(
+                    "Failed to serve behavior protocol on {}: {}",
+                    self.addr, err
+                );
+            }
+        }
+    }
+}
+
+/// Connect the behavior inspector of this app to a remote app serving the
+/// behavior protocol. Add it before `BehaviorInspectorPlugin`.
+pub struct BehaviorRemoteClientPlugin<T: BehaviorFactory> {
+    pub addr: Cow<'static, str>,
+    pub phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: BehaviorFactory> BehaviorRemoteClientPlugin<T> {
+    pub fn new(addr: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            addr: addr.into(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<T> Plugin for BehaviorRemoteClientPlugin<T>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(connect::<T>(self.addr.to_string()));
+    }
+}
+
+/// Listen for protocol clients on `addr`, one client at a time. Returns the
+/// server end of the protocol and the address it listens on. Messages sent
+/// while no client is connected are dropped, the server sends files and complete
+/// telemetry again when a client connects.
+pub fn listen<T>(addr: &str) -> io::Result<(BehaviorServer<T>, SocketAddr)>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    let listener = TcpListener::bind(addr)?;
+    let addr = listener.local_addr()?;
+    // Poll for clients, so messages can be dropped while there are none
+    listener.set_nonblocking(true)?;
+
+    let (server_sender, server_receiver) = unbounded::<BehaviorProtocolServer<T>>();
+    let (client_sender, client_receiver) = unbounded::<BehaviorProtocolClient<T>>();
+
+    thread::spawn(move || loop {
+        match listener.accept() {
+            Ok((stream, peer_addr)) => {
+                info!("Behavior client connected: {}", peer_addr);
+                if client_sender
+                    .send(BehaviorProtocolClient::Connected)
+                    .is_err()
+                {
+                    // App is gone
+                    return;
+                }
+                let result = stream
+                    .set_nonblocking(false)
+                    .and_then(|_| run_connection(stream, &server_receiver, &client_sender));
+                if let Err(err) = result {
+                    warn!("Behavior client connection failed: {}", err);
+                }
+                info!("Behavior client disconnected");
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                // No client to deliver to, drop messages until one connects
+                if !drop_messages(&server_receiver) {
+                    // App is gone
+                    return;
+                }
+                thread::sleep(ACCEPT_DELAY);
+            }
+            Err(err) => {
+                warn!("Behavior client failed to connect: {}", err);
+                thread::sleep(ACCEPT_DELAY);
+            }
+        }
+    });
+
+    let server = BehaviorServer {
+        sender: server_sender,
+        receiver: client_receiver,
+    };
+    Ok((server, addr))
+}
+
+/// Connect to a protocol server on `addr`. Returns the client end of the
+/// protocol right away, it keeps connecting again until the server is up.
+/// Messages sent while not connected are dropped, like on the server.
+pub fn connect<T>(addr: String) -> BehaviorClient<T>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    let (server_sender, server_receiver) = unbounded::<BehaviorProtocolServer<T>>();
+    let (client_sender, client_receiver) = unbounded::<BehaviorProtocolClient<T>>();
+
+    thread::spawn(move || loop {
+        match TcpStream::connect(&addr) {
+            Ok(stream) => {
+                info!("Connected to behavior server on {}", addr);
+                if let Err(err) = run_connection(stream, &client_receiver, &server_sender) {
+                    warn!("Behavior server connection failed: {}", err);
+                }
+                info!("Disconnected from behavior server on {}", addr);
+            }
+            Err(err) => {
+                debug!("Failed to connect to behavior server on {}: {}", addr, err);
+            }
+        }
+        thread::sleep(RECONNECT_DELAY);
+        // No server to deliver to, drop messages sent while disconnected
+        if !drop_messages(&client_receiver) {
+            // App is gone
+            return;
+        }
+    });
+
+    BehaviorClient {
+        sender: client_sender,
+        receiver: server_receiver,
+    }
+}
+
+/// Drop all pending messages. Returns false if the sender is gone.
+fn drop_messages<T>(receiver: &Receiver<T>) -> bool {
+    loop {
+        match receiver.try_recv() {
+            Ok(_) => {}
+            Err(TryRecvError::Empty) => return true,
+            Err(TryRecvError::Disconnected) => return false,
+        }
+    }
+}
+
+/// Pump messages over a connection until it is closed. Messages are RON, one
+/// per line. Outgoing messages are written from another thread.
+fn run_connection<Out, In>(
+    stream: TcpStream,
+    outgoing: &Receiver<Out>,
+    incoming: &Sender<In>,
+) -> io::Result<()>
+where
+    Out: Serialize + Send + 'static,
+    In: DeserializeOwned,
+{
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+    let outgoing = outgoing.clone();
+    let (closed_sender, closed_receiver) = unbounded::<()>();
+    let writer_thread = thread::spawn(move || loop {
+        let message = select! {
+            recv(outgoing) -> message => message.ok(),
+            // Reader is done, connection is closed
+            recv(closed_receiver) -> _ => None,
+        };
+        let Some(message) = message else { break };
+        let line = match ron::to_string(&message) {
+            Ok(line) => line,
+            Err(err) => {
+                error!("Failed to serialize behavior message: {}", err);
+                continue;
+            }
+        };
+        if writer
+            .write_all(line.as_bytes())
+            .and_then(|_| writer.write_all(b"\n"))
+            .is_err()
+        {
+            // Unblock the reader, the connection is no good anymore
+            let _ = writer.shutdown(Shutdown::Both);
+            break;
+        }
+    });
+
+    let reader = BufReader::new(stream);
+    let mut result = Ok(());
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        };
+        match ron::from_str::<In>(&line) {
+            Ok(message) => {
+                if incoming.send(message).is_err() {
+                    // App is gone
+                    break;
+                }
+            }
+            Err(err) => {
+                error!("Failed to deserialize behavior message: {}", err);
+            }
+        }
+    }
+
+    drop(closed_sender);
+    let _ = writer_thread.join();
+    result
+}
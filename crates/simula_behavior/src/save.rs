@@ -0,0 +1,373 @@
+use crate::{
+    asset::BehaviorTreeReset, blackboard::BlackboardSnapshot, tick::BehaviorClock,
+    BehaviorChildren, BehaviorCursor, BehaviorFactory, BehaviorFailure, BehaviorPaused,
+    BehaviorRunning, BehaviorStarted, BehaviorStopped, BehaviorSuccess, BehaviorTree,
+};
+use bevy::{
+    ecs::{entity::EntityMap, system::CommandQueue},
+    prelude::*,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use simula_core::prng::Prng;
+use simula_script::ScriptContext;
+use std::path::Path;
+
+/// Runtime fields of a behavior node, saved with its tree as RON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorNodeState(pub String);
+
+impl BehaviorNodeState {
+    /// Save runtime fields, usually as a tuple
+    pub fn new<S: Serialize>(fields: &S) -> Option<Self> {
+        ron::to_string(fields).ok().map(Self)
+    }
+
+    /// Read runtime fields saved with `new`
+    pub fn get<S: DeserializeOwned>(&self) -> Option<S> {
+        ron::from_str(&self.0).ok()
+    }
+}
+
+/// Time saved in a node state, relative to the behavior clock when saved, so it
+/// keeps its meaning in an app whose clock is at another time
+pub fn save_time(clock: &BehaviorClock, time: f64) -> f64 {
+    time - clock.elapsed_seconds_f64()
+}
+
+/// Time saved with `save_time`, rebased on the behavior clock when restored
+pub fn restore_time(clock: &BehaviorClock, time: f64) -> f64 {
+    clock.elapsed_seconds_f64() + time
+}
+
+/// Map an entity saved in a node state to the restored node, entities that
+/// are not nodes of the tree are kept
+pub fn map_entity(entity_map: &EntityMap, bits: u64) -> Entity {
+    let entity = Entity::from_bits(bits);
+    entity_map.get(entity).unwrap_or(entity)
+}
+
+/// Runtime field of a node, saved by `#[behavior(save)]` of the `BehaviorSpec` derive
+pub trait BehaviorSaveField: Sized {
+    /// How the field is saved
+    type Saved: Serialize + DeserializeOwned;
+
+    fn save_field(&self) -> Self::Saved;
+
+    fn restore_field(saved: Self::Saved, entity_map: &EntityMap) -> Self;
+}
+
+macro_rules! impl_save_field {
+    ($($ty:ty),*) => {
+        $(
+            impl BehaviorSaveField for $ty {
+                type Saved = $ty;
+
+                fn save_field(&self) -> Self::Saved {
+                    *self
+                }
+
+                fn restore_field(saved: Self::Saved, _entity_map: &EntityMap) -> Self {
+                    saved
+                }
+            }
+        )*
+    };
+}
+
+impl_save_field!(bool, u32, u64, usize, i32, i64, f32, f64);
+
+impl BehaviorSaveField for Entity {
+    type Saved = u64;
+
+    fn save_field(&self) -> Self::Saved {
+        self.to_bits()
+    }
+
+    fn restore_field(saved: Self::Saved, entity_map: &EntityMap) -> Self {
+        map_entity(entity_map, saved)
+    }
+}
+
+impl BehaviorSaveField for Prng {
+    type Saved = (u64, u64);
+
+    // The generator continues where it was, so choices after a restore are the
+    // same as without a save
+    fn save_field(&self) -> Self::Saved {
+        self.state()
+    }
+
+    fn restore_field(saved: Self::Saved, _entity_map: &EntityMap) -> Self {
+        Prng::from_state(saved)
+    }
+}
+
+impl<T: BehaviorSaveField> BehaviorSaveField for Option<T> {
+    type Saved = Option<T::Saved>;
+
+    fn save_field(&self) -> Self::Saved {
+        self.as_ref().map(T::save_field)
+    }
+
+    fn restore_field(saved: Self::Saved, entity_map: &EntityMap) -> Self {
+        saved.map(|saved| T::restore_field(saved, entity_map))
+    }
+}
+
+impl<T: BehaviorSaveField> BehaviorSaveField for Vec<T> {
+    type Saved = Vec<T::Saved>;
+
+    fn save_field(&self) -> Self::Saved {
+        self.iter().map(T::save_field).collect()
+    }
+
+    fn restore_field(saved: Self::Saved, entity_map: &EntityMap) -> Self {
+        saved
+            .into_iter()
+            .map(|saved| T::restore_field(saved, entity_map))
+            .collect()
+    }
+}
+
+/// Time field of a node, saved by `#[behavior(save_time)]` of the `BehaviorSpec`
+/// derive with `save_time` and restored with `restore_time`
+pub trait BehaviorSaveTime: Sized + Serialize + DeserializeOwned {
+    fn save_time(&self, clock: &BehaviorClock) -> Self;
+
+    fn restore_time(self, clock: &BehaviorClock) -> Self;
+}
+
+impl BehaviorSaveTime for f64 {
+    fn save_time(&self, clock: &BehaviorClock) -> Self {
+        save_time(clock, *self)
+    }
+
+    fn restore_time(self, clock: &BehaviorClock) -> Self {
+        restore_time(clock, self)
+    }
+}
+
+impl<T: BehaviorSaveTime> BehaviorSaveTime for Option<T> {
+    fn save_time(&self, clock: &BehaviorClock) -> Self {
+        self.as_ref().map(|time| time.save_time(clock))
+    }
+
+    fn restore_time(self, clock: &BehaviorClock) -> Self {
+        self.map(|time| time.restore_time(clock))
+    }
+}
+
+/// Execution state of a behavior node, saved with its tree
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BehaviorNodeSave {
+    /// Entity of the node when saved, to map entities saved in node states
+    pub entity: u64,
+    pub name: String,
+    #[serde(default)]
+    pub cursor: Option<BehaviorCursor>,
+    #[serde(default)]
+    pub running: bool,
+    #[serde(default)]
+    pub started: bool,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub failure: bool,
+    #[serde(default)]
+    pub state: Option<BehaviorNodeState>,
+}
+
+/// A running behavior tree saved to be restored later, like in a save game.
+/// Add it to a behavior tree and its state is restored once its nodes are
+/// built. A tree whose nodes do not match the saved nodes starts again.
+#[derive(Component, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BehaviorTreeSave {
+    /// Time of the behavior clock when saved, in seconds. Node times are saved
+    /// relative to it.
+    pub elapsed: f64,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub blackboard: BlackboardSnapshot,
+    /// Nodes from the root, depth first
+    pub nodes: Vec<BehaviorNodeSave>,
+}
+
+impl BehaviorTreeSave {
+    /// Save the state of a behavior tree, if its nodes are built
+    pub fn from_world<T: BehaviorFactory>(world: &World, tree: Entity) -> Option<Self> {
+        let root = *world.get::<Children>(tree)?.first()?;
+        let mut entities = vec![];
+        collect_nodes::<T>(world, root, &mut entities);
+
+        let nodes = entities
+            .iter()
+            .map(|&entity| save_node::<T>(world, entity))
+            .collect::<Option<Vec<_>>>()?;
+        let blackboard = world
+            .get::<Handle<ScriptContext>>(tree)
+            .and_then(|handle| world.get_resource::<Assets<ScriptContext>>()?.get(handle))
+            .map(BlackboardSnapshot::from_script_context)
+            .unwrap_or_default();
+        Some(Self {
+            elapsed: world.resource::<BehaviorClock>().elapsed_seconds_f64(),
+            paused: world.get::<BehaviorPaused>(tree).is_some(),
+            blackboard,
+            nodes,
+        })
+    }
+
+    /// Save as RON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        if let Some(dir) = path.as_ref().parent() {
+            std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        std::fs::write(path, data).map_err(|err| err.to_string())
+    }
+
+    /// Load a behavior tree saved with `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        ron::from_str(&data).map_err(|err| err.to_string())
+    }
+}
+
+// Collect a node and its children, depth first. Subtrees loaded at runtime
+// are not saved, they start again when restored.
+fn collect_nodes<T: BehaviorFactory>(world: &World, entity: Entity, entities: &mut Vec<Entity>) {
+    entities.push(entity);
+    if world.get::<BehaviorTree<T>>(entity).is_some() {
+        return;
+    }
+    if let Some(children) = world.get::<BehaviorChildren>(entity) {
+        for child in children.iter() {
+            collect_nodes::<T>(world, *child, entities);
+        }
+    }
+}
+
+// Data of a behavior node, found by trying each behavior type
+fn node_data<T: BehaviorFactory>(world: &World, entity: Entity) -> Option<T> {
+    T::list()
+        .into_iter()
+        .find_map(|mut data| data.copy_from(entity, world).ok().map(|_| data))
+}
+
+fn save_node<T: BehaviorFactory>(world: &World, entity: Entity) -> Option<BehaviorNodeSave> {
+    let mut node = BehaviorNodeSave {
+        entity: entity.to_bits(),
+        name: world.get::<Name>(entity)?.to_string(),
+        cursor: world.get::<BehaviorCursor>(entity).copied(),
+        running: world.get::<BehaviorRunning>(entity).is_some(),
+        started: world.get::<BehaviorStarted>(entity).is_some(),
+        paused: world.get::<BehaviorPaused>(entity).is_some(),
+        success: world.get::<BehaviorSuccess>(entity).is_some(),
+        failure: world.get::<BehaviorFailure>(entity).is_some(),
+        state: node_data::<T>(world, entity)
+            .and_then(|data| data.save_state(world.resource::<BehaviorClock>())),
+    };
+    // A running subtree loaded at runtime starts again
+    let active = node.running || node.cursor.is_some();
+    if active && world.get::<BehaviorTree<T>>(entity).is_some() {
+        node.cursor = Some(BehaviorCursor::Delegate);
+        node.running = false;
+        node.started = false;
+    }
+    Some(node)
+}
+
+/// Restore behavior trees with a `BehaviorTreeSave` once their nodes are built
+pub fn behavior_tree_load<T: BehaviorFactory>(world: &mut World) {
+    let mut saves = world.query_filtered::<
+        (Entity, &BehaviorTreeSave, &Children),
+        (With<BehaviorTree<T>>, Without<BehaviorTreeReset<T>>),
+    >();
+    let saves: Vec<(Entity, BehaviorTreeSave, Entity)> = saves
+        .iter(world)
+        .filter_map(|(tree, save, children)| Some((tree, save.clone(), *children.first()?)))
+        .collect();
+
+    for (tree, save, root) in saves {
+        world.entity_mut(tree).remove::<BehaviorTreeSave>();
+
+        let mut entities = vec![];
+        collect_nodes::<T>(world, root, &mut entities);
+        let matching = entities.len() == save.nodes.len()
+            && entities.iter().zip(&save.nodes).all(|(entity, node)| {
+                world
+                    .get::<Name>(*entity)
+                    .map_or(false, |name| name.as_str() == node.name)
+            });
+        if !matching {
+            warn!(
+                "Saved nodes do not match, restarting behavior tree for entity {:?}",
+                tree
+            );
+            continue;
+        }
+        info!("Restoring behavior tree for entity {:?}", tree);
+
+        // The saved blackboard replaces the one of the tree
+        let script_ctx = world.get::<Handle<ScriptContext>>(tree).cloned();
+        if let Some(script_ctx) = script_ctx {
+            let mut script_ctxs = world.resource_mut::<Assets<ScriptContext>>();
+            if let Some(script_ctx) = script_ctxs.get_mut(&script_ctx) {
+                save.blackboard.apply(script_ctx);
+            }
+        }
+
+        let mut entity_map = EntityMap::default();
+        for (entity, node) in entities.iter().zip(&save.nodes) {
+            entity_map.insert(Entity::from_bits(node.entity), *entity);
+        }
+
+        // Times saved by nodes are rebased on the clock, other trees keep theirs
+        let clock = world.resource::<BehaviorClock>().clone();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, world);
+        for (entity, node) in entities.iter().zip(&save.nodes) {
+            let mut node_commands = commands.entity(*entity);
+            node_commands.remove::<(
+                BehaviorCursor,
+                BehaviorRunning,
+                BehaviorStarted,
+                BehaviorPaused,
+                BehaviorStopped,
+                BehaviorSuccess,
+                BehaviorFailure,
+            )>();
+            if let Some(cursor) = node.cursor {
+                node_commands.insert(cursor);
+            }
+            if node.running {
+                node_commands.insert(BehaviorRunning);
+            }
+            if node.started {
+                node_commands.insert(BehaviorStarted);
+            }
+            if node.paused {
+                node_commands.insert(BehaviorPaused);
+            }
+            if node.success {
+                node_commands.insert(BehaviorSuccess);
+            }
+            if node.failure {
+                node_commands.insert(BehaviorFailure);
+            }
+            if let (Some(state), Some(mut data)) = (&node.state, node_data::<T>(world, *entity)) {
+                data.restore_state(state, &clock, &entity_map);
+                data.insert(&mut node_commands);
+            }
+        }
+        if save.paused {
+            commands.entity(tree).insert(BehaviorPaused);
+        } else {
+            commands.entity(tree).remove::<BehaviorPaused>();
+        }
+        command_queue.apply(world);
+    }
+}
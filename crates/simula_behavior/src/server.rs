@@ -3,13 +3,24 @@ use crate::{
     protocol::{
         BehaviorFileId, BehaviorFileName, BehaviorProtocolClient, BehaviorProtocolServer,
         BehaviorServer, BehaviorState, BehaviorTelemetry, RemoteEntity, StartOption, StopOption,
+        DEFAULT_TELEMETRY_RATE,
     },
+    stop_and_despawn,
+};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+    utils::HashMap,
 };
-use bevy::{prelude::*, utils::HashMap};
 use serde::{Deserialize, Serialize};
 use simula_script::ScriptContext;
 use std::borrow::Cow;
-use std::{cmp::Ordering, collections::BinaryHeap, time::Duration};
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 #[derive(Default)]
 pub struct BehaviorServerPlugin<T: BehaviorFactory>(pub std::marker::PhantomData<T>);
@@ -20,11 +31,20 @@ where
 {
     fn build(&self, app: &mut App) {
         app.insert_resource(BehaviorTrackers::<T>::default())
-            .add_startup_system(setup::<T>)
-            .add_system(track_loaded_behaviors::<T>)
-            .add_system(tracker_documents::<T>)
-            .add_system(update::<T>)
-            .add_system(update_telemetry::<T>);
+            .init_resource::<BehaviorServerConfig>()
+            // The server is disabled when there is no protocol to serve, like
+            // when a remote server failed to start
+            .add_systems(
+                (
+                    scan_behavior_files::<T>,
+                    track_loaded_behaviors::<T>,
+                    tracker_documents::<T>,
+                    update::<T>,
+                    update_breakpoints::<T>,
+                    update_telemetry::<T>,
+                )
+                    .distributive_run_if(resource_exists::<BehaviorServer<T>>()),
+            );
     }
 }
 
@@ -43,60 +63,283 @@ pub enum AssetTracker<T: BehaviorFactory> {
     Asset(Handle<BehaviorAsset<T>>),
 }
 
+/// Telemetry sent for a tracked behavior, so only changes are sent again
+#[derive(Clone)]
+pub struct TelemetryTracker<T: BehaviorFactory> {
+    /// Telemetry messages per second, 0 for every frame
+    pub rate: f32,
+    /// When telemetry was last sent
+    pub last_sent: Option<Duration>,
+    /// State and data last sent for each node
+    pub sent: HashMap<Entity, (BehaviorState, T)>,
+}
+
+impl<T: BehaviorFactory> Default for TelemetryTracker<T> {
+    fn default() -> Self {
+        Self {
+            rate: DEFAULT_TELEMETRY_RATE,
+            last_sent: None,
+            sent: HashMap::default(),
+        }
+    }
+}
+
+impl<T: BehaviorFactory> TelemetryTracker<T> {
+    /// Forget what was sent, so the next telemetry is complete
+    pub fn reset(&mut self) {
+        self.last_sent = None;
+        self.sent.clear();
+    }
+
+    /// Check if telemetry is due at `now`
+    pub fn is_due(&self, now: Duration) -> bool {
+        match self.last_sent {
+            Some(last_sent) if self.rate > 0.0 => {
+                now.saturating_sub(last_sent) >= Duration::from_secs_f32(1.0 / self.rate)
+            }
+            _ => true,
+        }
+    }
+
+    /// Build telemetry of the nodes under `root` that changed since last sent,
+    /// and mark it sent at `now`. Returns `None` if nothing changed. Complete
+    /// telemetry is built next time if it fails.
+    pub fn build(
+        &mut self,
+        world: &World,
+        root: Entity,
+        behavior: &Behavior<T>,
+        now: Duration,
+    ) -> Result<Option<BehaviorTelemetry<T>>, BehaviorMissing> {
+        let mut last_sent = std::mem::take(&mut self.sent);
+        let mut telemetry = BehaviorTelemetry::<T>::default();
+        self.last_sent = Some(now);
+        let result = build_telemetry(
+            world,
+            root,
+            &mut telemetry,
+            behavior,
+            &mut last_sent,
+            &mut self.sent,
+        );
+        if result.is_err() {
+            self.sent.clear();
+        }
+        result.map(|_| telemetry.0.is_some().then_some(telemetry))
+    }
+}
+
 #[derive(Clone)]
 pub struct BehaviorTracker<T: BehaviorFactory> {
     pub file_name: BehaviorFileName,
     pub entity: EntityTracker,
     pub asset: AssetTracker<T>,
+    pub telemetry: TelemetryTracker<T>,
+    /// Nodes to pause at, as paths of child indices from the root
+    pub breakpoints: Vec<Vec<usize>>,
+    /// The client added a recorder to the behavior, removed when it stops
+    pub recorder: bool,
+}
+
+/// Where the behavior server looks for behavior files
+#[derive(Resource, Debug, Clone)]
+pub struct BehaviorServerConfig {
+    /// Asset folder, behavior files are loaded and saved relative to it
+    pub asset_folder: Cow<'static, str>,
+    /// Folders with behavior files, relative to the asset folder. Sub folders are
+    /// searched too.
+    pub roots: Vec<Cow<'static, str>>,
+    /// How often the roots are scanned for new and removed files, `None` to only
+    /// scan them at startup
+    pub scan_interval: Option<Duration>,
+}
+
+impl Default for BehaviorServerConfig {
+    fn default() -> Self {
+        Self {
+            asset_folder: "assets".into(),
+            roots: vec!["bht/u".into()],
+            scan_interval: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+impl BehaviorServerConfig {
+    pub fn new<R>(roots: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Into<Cow<'static, str>>,
+    {
+        Self {
+            roots: roots.into_iter().map(Into::into).collect(),
+            ..default()
+        }
+    }
+
+    pub fn with_asset_folder(mut self, asset_folder: impl Into<Cow<'static, str>>) -> Self {
+        self.asset_folder = asset_folder.into();
+        self
+    }
+
+    pub fn with_scan_interval(mut self, scan_interval: Option<Duration>) -> Self {
+        self.scan_interval = scan_interval;
+        self
+    }
+
+    /// Behavior files in the roots and their sub folders, sorted. File names are
+    /// relative to the asset folder, without extension, e.g. `bht/u/agent`.
+    pub fn discover(&self) -> Vec<BehaviorFileName> {
+        let asset_folder = Path::new(self.asset_folder.as_ref());
+        let mut files = vec![];
+        for root in &self.roots {
+            let dir = asset_folder.join(root.as_ref());
+            if let Err(err) = find_behavior_files(&dir, &mut files) {
+                warn!("Failed to read behavior folder {}: {}", dir.display(), err);
+            }
+        }
+        let mut file_names: Vec<String> = files
+            .iter()
+            .filter_map(|path| {
+                let path = path.strip_prefix(asset_folder).ok()?;
+                let path = path.to_string_lossy().replace('\\', "/");
+                Some(path.trim_end_matches(".bht.ron").to_string())
+            })
+            .collect();
+        file_names.sort();
+        file_names.dedup();
+        file_names
+            .into_iter()
+            .map(|file_name| BehaviorFileName(file_name.into()))
+            .collect()
+    }
 }
 
 #[derive(Default, Resource, Deref, DerefMut)]
 pub struct BehaviorTrackers<T: BehaviorFactory>(HashMap<BehaviorFileId, BehaviorTracker<T>>);
 
-fn setup<T: BehaviorFactory>(
+// Track behavior files found in the roots, and forget the ones removed. Roots
+// are scanned on the `IoTaskPool`, so slow disks don't stall the app.
+fn scan_behavior_files<T: BehaviorFactory>(
+    time: Res<Time>,
+    config: Res<BehaviorServerConfig>,
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
     behavior_server: Res<BehaviorServer<T>>,
+    behavior_trees: Query<(), With<BehaviorTree<T>>>,
+    mut last_scan: Local<Option<Duration>>,
+    mut scan: Local<Option<Task<Vec<BehaviorFileName>>>>,
+    mut discovered: Local<Vec<BehaviorFileName>>,
 ) {
-    let dir_path = "assets/bht/u";
-
-    // Read the directory and handle any errors
-    let paths = match std::fs::read_dir(dir_path) {
-        Ok(paths) => paths,
-        Err(err) => {
-            eprintln!("Error reading directory: {}", err);
-            return;
+    // Start a scan when due, unless one is running
+    if scan.is_none() {
+        let now = time.elapsed();
+        match (*last_scan, config.scan_interval) {
+            (None, _) => {}
+            (Some(last_scan), Some(scan_interval)) if now - last_scan >= scan_interval => {}
+            _ => return,
         }
+        *last_scan = Some(now);
+        let config = config.clone();
+        *scan = Some(IoTaskPool::get().spawn(async move { config.discover() }));
+    }
+    let Some(file_names) = scan
+        .as_mut()
+        .and_then(|task| future::block_on(future::poll_once(task)))
+    else {
+        return;
     };
+    *scan = None;
 
-    // Iterate over the directory entries
-    for path in paths {
-        if let Ok(entry) = path {
-            // Check if the entry is a file with the desired extension
-            if entry.file_type().unwrap().is_file() {
-                let osfile_name = entry.file_name();
-                let file_name = osfile_name.to_string_lossy().to_owned();
-                if file_name.ends_with(".bht.ron") {
-                    let file_id = BehaviorFileId::new();
-                    let file_name = format!("bht/u/{}", file_name.trim_end_matches(".bht.ron"));
-                    let file_name = BehaviorFileName(file_name.to_string().into());
-
-                    behavior_trackers.insert(
-                        file_id.clone(),
-                        BehaviorTracker {
-                            file_name: file_name.clone(),
-                            entity: EntityTracker::None,
-                            asset: AssetTracker::None,
-                        },
-                    );
+    // Track new files
+    for file_name in &file_names {
+        let tracked = behavior_trackers
+            .values()
+            .any(|behavior_tracker| behavior_tracker.file_name == *file_name);
+        if tracked {
+            continue;
+        }
+        let file_id = BehaviorFileId::new();
+        behavior_trackers.insert(
+            file_id.clone(),
+            BehaviorTracker {
+                file_name: file_name.clone(),
+                entity: EntityTracker::None,
+                asset: AssetTracker::None,
+                telemetry: TelemetryTracker::default(),
+                breakpoints: vec![],
+                recorder: false,
+            },
+        );
+        behavior_server
+            .sender
+            .send(BehaviorProtocolServer::FileName(file_id, file_name.clone()))
+            .unwrap();
+    }
 
-                    behavior_server
-                        .sender
-                        .send(BehaviorProtocolServer::FileName(file_id, file_name))
-                        .unwrap();
+    // Forget removed files, only the ones found by a previous scan. Files with
+    // a running behavior are kept until it stops.
+    let mut running = vec![];
+    for file_name in discovered.iter() {
+        if file_names.contains(file_name) {
+            continue;
+        }
+        let file_ids: Vec<BehaviorFileId> = behavior_trackers
+            .iter()
+            .filter(|(_, behavior_tracker)| behavior_tracker.file_name == *file_name)
+            .map(|(file_id, _)| file_id.clone())
+            .collect();
+        for file_id in file_ids {
+            let entity = match behavior_trackers[&file_id].entity {
+                EntityTracker::Spawned(entity) => Some(entity),
+                EntityTracker::Attached(entity) => Some(entity),
+                EntityTracker::Inserted(entity) => Some(entity),
+                EntityTracker::None => None,
+            };
+            if entity.map_or(false, |entity| behavior_trees.contains(entity)) {
+                if !running.contains(file_name) {
+                    running.push(file_name.clone());
                 }
+                continue;
             }
+            info!("Removed: {:?} {}", file_id, file_name.as_ref());
+            behavior_trackers.remove(&file_id);
+            behavior_server
+                .sender
+                .send(BehaviorProtocolServer::FileRemoved(file_id))
+                .unwrap();
         }
     }
+    *discovered = file_names;
+    // Check the running ones again on the next scan
+    discovered.extend(running);
+}
+
+// Find `*.bht.ron` files in a folder and its sub folders
+fn find_behavior_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_behavior_files(&path, files)?;
+        } else if path.to_string_lossy().ends_with(".bht.ron") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Get the blackboard schema of a tracked behavior asset
+fn tracker_blackboard<T: BehaviorFactory>(
+    behavior_tracker: Option<&BehaviorTracker<T>>,
+    behavior_assets: &Assets<BehaviorAsset<T>>,
+) -> BlackboardSchema {
+    if let Some(BehaviorTracker {
+        asset: AssetTracker::Asset(behavior_asset),
+        ..
+    }) = behavior_tracker
+    {
+        if let Some(behavior_asset) = behavior_assets.get(behavior_asset) {
+            return behavior_asset.blackboard.clone();
+        }
+    }
+    BlackboardSchema::default()
 }
 
 // Convert AssetTracker::Document to AssetTracker::Asset
@@ -109,8 +352,8 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
     for (_file_id, tracker) in behavior_trackers.iter_mut() {
         if let AssetTracker::Document(document_handle) = &tracker.asset {
             if let Some(document) = behavior_documents.get(document_handle) {
-                let res = ron::de::from_str::<Behavior<T>>(&document);
-                if let Ok(behavior) = res {
+                let res = BehaviorFile::<T>::from_document(&document);
+                if let Ok(behavior_file) = res {
                     // Get file name
                     let path = asset_server.get_handle_path(document_handle);
                     let file_name = path.and_then(|path| {
@@ -122,7 +365,8 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
 
                     // Add behavior asset to asset manager
                     let behavior_handle = behavior_assets.add(BehaviorAsset {
-                        behavior,
+                        behavior: behavior_file.root,
+                        blackboard: behavior_file.blackboard,
                         file_name,
                     });
 
@@ -139,8 +383,6 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
 }
 
 fn track_loaded_behaviors<T: BehaviorFactory>(
-    mut commands: Commands,
-    mut behavior_trees: Query<(Entity, &Name, &Handle<BehaviorAsset<T>>), With<BehaviorTree<T>>>,
     mut asset_events: EventReader<AssetEvent<BehaviorAsset<T>>>,
     asset_server: Res<AssetServer>,
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
@@ -180,6 +422,9 @@ fn track_loaded_behaviors<T: BehaviorFactory>(
                             file_name: behavior_file_name.clone(),
                             entity: EntityTracker::None,
                             asset: AssetTracker::Asset(handle.clone()),
+                            telemetry: TelemetryTracker::default(),
+                            breakpoints: vec![],
+                            recorder: false,
                         },
                     );
 
@@ -193,20 +438,11 @@ fn track_loaded_behaviors<T: BehaviorFactory>(
                         .unwrap();
                 }
             }
+            // Trees are rebuilt by `BehaviorTreePlugin`, see `BehaviorHotReload`
             AssetEvent::Modified { handle } => {
                 if let Some(path) = asset_server.get_handle_path(handle) {
                     info!("Modified: {:?}", path);
                 }
-                for (entity, name, behavior_asset) in &mut behavior_trees {
-                    if behavior_asset == handle {
-                        if let Some(_asset) = behavior_assets.get(&handle) {
-                            info!("Rebuilding behavior for: [{}] {}", entity.index(), name);
-                            commands
-                                .entity(entity)
-                                .insert(BehaviorTreeReset::<T>::default());
-                        }
-                    }
-                }
             }
             _ => {
                 error!("{:?}", event);
@@ -216,17 +452,24 @@ fn track_loaded_behaviors<T: BehaviorFactory>(
     }
 }
 
-fn build_telemetry<T: BehaviorFactory>(
-    world: &mut World,
+// Build telemetry of the nodes that changed since `last_sent`, and move what is
+// sent into `sent`. Telemetry is left as default if nothing changed.
+pub(crate) fn build_telemetry<T: BehaviorFactory>(
+    world: &World,
     entity: Entity,
     telemetry: &mut BehaviorTelemetry<T>,
     behavior: &Behavior<T>,
+    last_sent: &mut HashMap<Entity, (BehaviorState, T)>,
+    sent: &mut HashMap<Entity, (BehaviorState, T)>,
 ) -> Result<(), BehaviorMissing> {
     let behavior_running = world.get::<BehaviorRunning>(entity);
     let behavior_failure = world.get::<BehaviorFailure>(entity);
     let behavior_success = world.get::<BehaviorSuccess>(entity);
     let behavior_cursor = world.get::<BehaviorCursor>(entity);
-    let behavior_state = if behavior_cursor.is_some() {
+    let behavior_paused = world.get::<BehaviorPaused>(entity);
+    let behavior_state = if behavior_cursor.is_some() && behavior_paused.is_some() {
+        BehaviorState::Paused
+    } else if behavior_cursor.is_some() {
         BehaviorState::Cursor
     } else if behavior_running.is_some() {
         BehaviorState::Running
@@ -239,10 +482,27 @@ fn build_telemetry<T: BehaviorFactory>(
     };
 
     // Copy data from entity to telemetry
-    let mut data = behavior.data().clone();
-    data.copy_from(entity, world)?;
+    let copy_data = || {
+        let mut data = behavior.data().clone();
+        data.copy_from(entity, world).map(|_| data)
+    };
+
+    // Compare with what was sent last, data is only copied when it changed
+    let (state_changed, data_changed, data) = match last_sent.remove(&entity) {
+        Some((last_state, last_data)) => {
+            let state_changed = last_state != behavior_state;
+            match last_data.compare_from(entity, world)? {
+                Some(true) => (state_changed, false, last_data),
+                Some(false) => (state_changed, true, copy_data()?),
+                // Data can't be compared with reflection, send it as changed
+                None => (state_changed, true, copy_data()?),
+            }
+        }
+        None => (true, true, copy_data()?),
+    };
 
     let mut telemetry_children = vec![];
+    let mut children_changed = false;
 
     let instance_children = world.get::<BehaviorChildren>(entity).cloned();
     let source_children = behavior.nodes().iter();
@@ -250,22 +510,88 @@ fn build_telemetry<T: BehaviorFactory>(
         let instance_children = instance_children.iter();
         for (instance_child, source_child) in instance_children.zip(source_children) {
             let mut telemetry = BehaviorTelemetry::<T>::default();
-            build_telemetry(world, *instance_child, &mut telemetry, source_child)?;
+            build_telemetry(
+                world,
+                *instance_child,
+                &mut telemetry,
+                source_child,
+                last_sent,
+                sent,
+            )?;
+            children_changed |= telemetry.0.is_some();
             telemetry_children.push(telemetry);
         }
     }
 
-    *telemetry = BehaviorTelemetry(
-        Some(RemoteEntity::new(entity, "")),
-        behavior_state,
-        Some(data),
-        telemetry_children,
-    );
+    if state_changed || data_changed || children_changed {
+        *telemetry = BehaviorTelemetry(
+            Some(RemoteEntity::new(entity, "")),
+            behavior_state,
+            data_changed.then(|| data.clone()),
+            telemetry_children,
+        );
+    }
+    sent.insert(entity, (behavior_state, data));
 
     Ok(())
 }
 
+// Mark the nodes at the breakpoints of each tracked behavior
+fn update_breakpoints<T: BehaviorFactory>(
+    mut commands: Commands,
+    behavior_trackers: Res<BehaviorTrackers<T>>,
+    behavior_trees: Query<&Children, With<BehaviorTree<T>>>,
+    behavior_children: Query<&BehaviorChildren>,
+    breakpoints: Query<(Entity, &BehaviorNode), With<BehaviorBreakpoint>>,
+) {
+    for behavior_tracker in behavior_trackers.values() {
+        let entity = match behavior_tracker.entity {
+            EntityTracker::Spawned(entity) => entity,
+            EntityTracker::Attached(entity) => entity,
+            EntityTracker::Inserted(entity) => entity,
+            EntityTracker::None => continue,
+        };
+        let Some(root) = behavior_trees
+            .get(entity)
+            .ok()
+            .and_then(|children| children.first().copied())
+        else {
+            continue;
+        };
+
+        // Find nodes by following child indices from the root
+        let nodes: Vec<Entity> = behavior_tracker
+            .breakpoints
+            .iter()
+            .filter_map(|path| {
+                path.iter().try_fold(root, |node, index| {
+                    behavior_children
+                        .get(node)
+                        .ok()
+                        .and_then(|children| children.get(*index).copied())
+                })
+            })
+            .collect();
+
+        for (node_entity, node) in &breakpoints {
+            if node.tree == entity && !nodes.contains(&node_entity) {
+                commands.entity(node_entity).remove::<BehaviorBreakpoint>();
+            }
+        }
+        for node in nodes {
+            if !breakpoints.contains(node) {
+                commands.entity(node).insert(BehaviorBreakpoint);
+            }
+        }
+    }
+}
+
 fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
+    let now = world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default();
+
     let mut tracks = vec![];
     if let Some(behavior_trackers) = world.get_resource::<BehaviorTrackers<T>>() {
         for (file_id, behavior_tracker) in behavior_trackers.iter() {
@@ -275,16 +601,12 @@ fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
                 EntityTracker::Inserted(entity) => Some(entity),
                 EntityTracker::None => continue,
             };
+            if !behavior_tracker.telemetry.is_due(now) {
+                continue;
+            }
             if let Some(entity) = entity {
                 if let Some(behavior_asset) = world.get::<Handle<BehaviorAsset<T>>>(entity) {
-                    if let Some(behavior_assets) = world.get_resource::<Assets<BehaviorAsset<T>>>()
-                    {
-                        if let Some(behavior_asset) = behavior_assets.get(&behavior_asset) {
-                            tracks.push((file_id.clone(), entity, behavior_asset.behavior.clone()));
-                        }
-                    } else {
-                        error!("Failed to get behavior assets");
-                    }
+                    tracks.push((file_id.clone(), entity, behavior_asset.clone()));
                 } else {
                     // Behavior has no asset yet
                 }
@@ -298,23 +620,58 @@ fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
 
     let mut behaviors_children = world.query_filtered::<&Children, With<BehaviorTree<T>>>();
 
-    for (file_id, entity, behavior) in tracks {
+    for (file_id, entity, behavior_asset) in tracks {
         let mut root = None;
         if let Ok(children) = behaviors_children.get(world, entity) {
-            root = children.first();
+            root = children.first().copied();
         }
-        if let Some(root) = root {
-            let mut telemetry = BehaviorTelemetry::<T>::default();
-            if build_telemetry(world, *root, &mut telemetry, &behavior).is_ok() {
-                let behavior_server = world.get_resource::<BehaviorServer<T>>().unwrap();
-                behavior_server
-                    .sender
-                    .send(BehaviorProtocolServer::Telemetry(file_id, telemetry))
-                    .unwrap();
-            } else {
+        let Some(root) = root else {
+            continue;
+        };
+
+        let Some(mut telemetry_tracker) = world
+            .resource_mut::<BehaviorTrackers<T>>()
+            .get_mut(&file_id)
+            .map(|behavior_tracker| std::mem::take(&mut behavior_tracker.telemetry))
+        else {
+            continue;
+        };
+        // Borrow the behavior from its asset, instead of cloning it
+        let result = world
+            .get_resource::<Assets<BehaviorAsset<T>>>()
+            .and_then(|behavior_assets| behavior_assets.get(&behavior_asset))
+            .map(|behavior_asset| {
+                telemetry_tracker.build(world, root, &behavior_asset.behavior, now)
+            });
+        if result.is_none() {
+            // Send complete telemetry next time
+            telemetry_tracker.reset();
+        }
+        if let Some(behavior_tracker) = world
+            .resource_mut::<BehaviorTrackers<T>>()
+            .get_mut(&file_id)
+        {
+            behavior_tracker.telemetry = telemetry_tracker;
+        }
+
+        let telemetry = match result {
+            Some(Ok(Some(telemetry))) => telemetry,
+            // Nothing changed since last sent
+            Some(Ok(None)) => continue,
+            Some(Err(_)) => {
                 error!("Failed to build telemetry");
+                continue;
             }
-        }
+            None => {
+                error!("Failed to get behavior asset");
+                continue;
+            }
+        };
+        let behavior_server = world.get_resource::<BehaviorServer<T>>().unwrap();
+        behavior_server
+            .sender
+            .send(BehaviorProtocolServer::Telemetry(file_id, telemetry))
+            .unwrap();
     }
 }
 
@@ -360,11 +717,18 @@ fn update<T>(
         (Entity, &Name, Option<&Handle<BehaviorAsset<T>>>),
         With<BehaviorTree<T>>,
     >,
+    behavior_recorders: Query<&BehaviorRecorder<T>>,
+    tree_children: Query<&Children>,
+    running_nodes: Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
+    >,
     mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
     mut script_ctxs: ResMut<Assets<ScriptContext>>,
     behavior_server: Res<BehaviorServer<T>>,
     asset_server: Res<AssetServer>,
+    config: Res<BehaviorServerConfig>,
     mut queued_msgs: Local<PriorityMessageQueue<T>>,
 ) where
     T: BehaviorFactory + Serialize,
@@ -393,6 +757,20 @@ fn update<T>(
     // Process all server messages
     while let Some(msg) = &queued_msgs.pop() {
         match &msg.msg {
+            BehaviorProtocolClient::Connected => {
+                info!("Received Connected");
+                // List files again and send complete telemetry to the new client
+                for (file_id, behavior_tracker) in behavior_trackers.iter_mut() {
+                    behavior_tracker.telemetry.reset();
+                    behavior_server
+                        .sender
+                        .send(BehaviorProtocolServer::FileName(
+                            file_id.clone(),
+                            behavior_tracker.file_name.clone(),
+                        ))
+                        .unwrap();
+                }
+            }
             BehaviorProtocolClient::Instances(file_id) => {
                 info!("Received Instances: {:?}", file_id);
                 if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
@@ -501,24 +879,40 @@ fn update<T>(
             }
             BehaviorProtocolClient::SaveFile(file_id, file_name, file_data) => {
                 info!("Received SaveFile: {:?} {}", file_id, file_name.as_ref());
+                // keep the blackboard schema of the tracked asset
+                let blackboard =
+                    tracker_blackboard(behavior_trackers.get(&file_id), &behavior_assets);
                 // let file_data = serde_yaml::to_string(&file_data);
-                let file_data =
-                    ron::ser::to_string_pretty(&file_data, ron::ser::PrettyConfig::default());
+                let file_data = if blackboard.is_empty() {
+                    ron::ser::to_string_pretty(&file_data, ron::ser::PrettyConfig::default())
+                } else {
+                    let file_data = BehaviorFile {
+                        blackboard,
+                        root: file_data.clone(),
+                    };
+                    ron::ser::to_string_pretty(&file_data, ron::ser::PrettyConfig::default())
+                };
                 match file_data {
                     Ok(file_data) => {
                         // if we have a tracker, update the file_name
                         if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
                             behavior_tracker.file_name = file_name.clone();
                         }
-                        let dir_path = "assets";
-                        let file_ext = "bht.ron";
-                        let file_path = format!("{}/{}.{}", dir_path, file_name.as_ref(), file_ext);
-                        std::fs::write(&file_path, file_data).unwrap();
-                        info!("Saved file: {}", &file_path);
-                        behavior_server
-                            .sender
-                            .send(BehaviorProtocolServer::FileSaved(file_id.clone()))
-                            .unwrap();
+                        let file_path = Path::new(config.asset_folder.as_ref())
+                            .join(format!("{}.bht.ron", file_name.as_ref()));
+                        let saved = file_path
+                            .parent()
+                            .map_or(Ok(()), std::fs::create_dir_all)
+                            .and_then(|_| std::fs::write(&file_path, file_data));
+                        if let Err(err) = saved {
+                            error!("Failed to save file {}: {}", file_path.display(), err);
+                        } else {
+                            info!("Saved file: {}", file_path.display());
+                            behavior_server
+                                .sender
+                                .send(BehaviorProtocolServer::FileSaved(file_id.clone()))
+                                .unwrap();
+                        }
                     }
                     Err(err) => {
                         error!("Failed to serialize file_data: {:?}", err);
@@ -536,11 +930,16 @@ fn update<T>(
                 let mut behavior_tracker = None;
                 let mut behavior_asset = AssetTracker::None;
 
+                // keep the blackboard schema of the tracked asset
+                let blackboard =
+                    tracker_blackboard(behavior_trackers.get(&file_id), &behavior_assets);
+
                 // use the behavior passed in
                 if let Some(behavior) = behavior {
                     // create a new asset to hold the behavior
                     let a_behavior_asset = BehaviorAsset::<T> {
                         behavior: behavior.clone(),
+                        blackboard: blackboard.clone(),
                         file_name: None,
                     };
                     let handle = behavior_assets.add(a_behavior_asset);
@@ -560,6 +959,7 @@ fn update<T>(
                     if let Some(behavior) = behavior {
                         let a_behavior_asset = BehaviorAsset::<T> {
                             behavior: behavior.clone(),
+                            blackboard: blackboard.clone(),
                             file_name: None,
                         };
                         let handle = behavior_assets.add(a_behavior_asset);
@@ -568,6 +968,9 @@ fn update<T>(
                             file_name: file_name.clone(),
                             asset: AssetTracker::Asset(handle.clone()),
                             entity: EntityTracker::None,
+                            telemetry: TelemetryTracker::default(),
+                            breakpoints: vec![],
+                            recorder: false,
                         };
                         behavior_trackers.insert(file_id.clone(), a_behavior_tracker);
                         behavior_tracker = behavior_trackers.get_mut(&file_id);
@@ -578,6 +981,7 @@ fn update<T>(
                     (behavior_tracker, behavior_asset)
                 {
                     behavior_tracker.entity = EntityTracker::None;
+                    behavior_tracker.telemetry.reset();
 
                     match start_option {
                         // spawn behavior tree
@@ -653,23 +1057,38 @@ fn update<T>(
                     };
 
                     if let Some(entity) = entity {
-                        match stop_option {
-                            StopOption::Despawn => {
+                        if behavior_tracker.recorder {
+                            commands.entity(entity).remove::<BehaviorRecorder<T>>();
+                        }
+                        // Running nodes see `BehaviorStopped` before they are
+                        // despawned, so they release what they hold
+                        let root = tree_children
+                            .get(entity)
+                            .ok()
+                            .and_then(|children| children.first().copied());
+                        match (stop_option, root) {
+                            (StopOption::Despawn, Some(root)) => {
+                                stop_and_despawn(&mut commands, entity, root, &running_nodes);
+                            }
+                            (StopOption::Despawn, None) => {
                                 commands.entity(entity).despawn_recursive();
                             }
-                            StopOption::Detach => {}
-                            StopOption::Remove => {
+                            (StopOption::Detach, _) => {}
+                            (StopOption::Remove, root) => {
+                                if let Some(root) = root {
+                                    stop_and_despawn(&mut commands, root, root, &running_nodes);
+                                }
                                 commands
                                     .entity(entity)
                                     .remove::<Handle<BehaviorAsset<T>>>()
-                                    .remove::<Handle<ScriptContext>>()
-                                    .despawn_descendants();
-                                commands.entity(entity).clear_children();
+                                    .remove::<Handle<ScriptContext>>();
                             }
                         }
                     }
 
                     behavior_tracker.entity = EntityTracker::None;
+                    behavior_tracker.telemetry.reset();
+                    behavior_tracker.recorder = false;
                     behavior_server
                         .sender
                         .send(BehaviorProtocolServer::Stopped(file_id.clone()))
@@ -678,6 +1097,91 @@ fn update<T>(
                     error!("Invalid file_id: {:?}", file_id);
                 }
             }
+            BehaviorProtocolClient::TelemetryRate(file_id, rate) => {
+                info!("Received TelemetryRate: {:?} {}", file_id, rate);
+                if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
+                    behavior_tracker.telemetry.rate = rate.max(0.0);
+                    behavior_tracker.telemetry.reset();
+                } else {
+                    error!("Invalid file_id: {:?}", file_id);
+                }
+            }
+            BehaviorProtocolClient::Breakpoints(file_id, breakpoints) => {
+                info!("Received Breakpoints: {:?} {:?}", file_id, breakpoints);
+                if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
+                    behavior_tracker.breakpoints = breakpoints.clone();
+                } else {
+                    error!("Invalid file_id: {:?}", file_id);
+                }
+            }
+            BehaviorProtocolClient::Resume(file_id, resume) => {
+                info!("Received Resume: {:?} {:?}", file_id, resume);
+                if let Some(behavior_tracker) = behavior_trackers.get(&file_id) {
+                    match behavior_tracker.entity {
+                        EntityTracker::Spawned(entity)
+                        | EntityTracker::Attached(entity)
+                        | EntityTracker::Inserted(entity) => {
+                            commands.entity(entity).insert(*resume);
+                        }
+                        EntityTracker::None => {
+                            error!("Behavior is not running: {:?}", file_id);
+                        }
+                    }
+                } else {
+                    error!("Invalid file_id: {:?}", file_id);
+                }
+            }
+            BehaviorProtocolClient::Record(file_id, record) => {
+                info!("Received Record: {:?} {}", file_id, record);
+                if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
+                    let entity = match behavior_tracker.entity {
+                        EntityTracker::Spawned(entity) => Some(entity),
+                        EntityTracker::Attached(entity) => Some(entity),
+                        EntityTracker::Inserted(entity) => Some(entity),
+                        EntityTracker::None => None,
+                    };
+                    if let Some(entity) = entity {
+                        let recorded = behavior_recorders.contains(entity);
+                        if *record && !recorded {
+                            commands
+                                .entity(entity)
+                                .insert(BehaviorRecorder::<T>::default());
+                            behavior_tracker.recorder = true;
+                        } else if !*record && recorded {
+                            commands.entity(entity).remove::<BehaviorRecorder<T>>();
+                            behavior_tracker.recorder = false;
+                        }
+                    } else {
+                        error!("Behavior is not running: {:?}", file_id);
+                    }
+                } else {
+                    error!("Invalid file_id: {:?}", file_id);
+                }
+            }
+            BehaviorProtocolClient::Recording(file_id) => {
+                info!("Received Recording: {:?}", file_id);
+                if let Some(behavior_tracker) = behavior_trackers.get(&file_id) {
+                    let entity = match behavior_tracker.entity {
+                        EntityTracker::Spawned(entity) => Some(entity),
+                        EntityTracker::Attached(entity) => Some(entity),
+                        EntityTracker::Inserted(entity) => Some(entity),
+                        EntityTracker::None => None,
+                    };
+                    if let Some(recorder) = entity.and_then(|e| behavior_recorders.get(e).ok()) {
+                        behavior_server
+                            .sender
+                            .send(BehaviorProtocolServer::Recording(
+                                file_id.clone(),
+                                recorder.recording.clone(),
+                            ))
+                            .unwrap();
+                    } else {
+                        error!("Behavior is not recorded: {:?}", file_id);
+                    }
+                } else {
+                    error!("Invalid file_id: {:?}", file_id);
+                }
+            }
         }
 
         if let Some(msg) = queued_msgs.peek() {
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Who receives a signal
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SignalScope {
+    /// Only nodes in the same behavior tree
+    #[default]
+    Tree,
+    /// Nodes in all behavior trees
+    Global,
+}
+
+/// Event to raise a named signal, for a behavior tree or for all of them.
+/// Behavior trees and ordinary systems can send and read signals. Signals are
+/// kept until trees ticked in two frames, not for two frames like other events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BehaviorSignal {
+    pub name: Cow<'static, str>,
+    /// Tree entity to signal, or `None` to signal all trees
+    pub tree: Option<Entity>,
+}
+
+impl BehaviorSignal {
+    /// Signal all behavior trees
+    pub fn global(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            tree: None,
+        }
+    }
+
+    /// Signal a single behavior tree
+    pub fn tree(tree: Entity, name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            tree: Some(tree),
+        }
+    }
+
+    /// Check if a tree receives this signal
+    pub fn is_for(&self, tree: Entity, name: &str) -> bool {
+        self.name == name && self.tree.map_or(true, |signal_tree| signal_tree == tree)
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{clear_behavior_started, complete_behavior, prelude::*, start_behavior, BehaviorTrace};
+use crate::{
+    blackboard::{self, blackboard_changes},
+    prelude::*,
+    tick::init_behavior_schedule,
+    BehaviorTrace,
+};
 use bevy::{
     ecs::system::{CommandQueue, EntityCommands},
     prelude::*,
@@ -7,33 +12,68 @@ use bevy::{
 use serde::{Deserialize, Serialize};
 use simula_behavior_macro::BehaviorFactory;
 use simula_script::{Script, ScriptContext};
+use std::path::{Path, PathBuf};
 
 pub const MAX_ITERS: usize = 200;
 
+/// Clock step of each tick in tests, so traces don't depend on machine speed
+pub const TEST_TIMESTEP: f64 = 1.0 / 60.0;
+
+/// Set this env var to rewrite golden trace files, instead of checking them
+pub const BLESS_ENV: &str = "BEHAVIOR_BLESS";
+
 pub fn test_app(app: &mut App) -> &mut App {
     app.add_plugin(AssetPlugin::default());
     app.add_asset::<Script>();
     app.add_asset::<ScriptContext>();
+    app.add_event::<BlackboardChanged>();
+    // Random nodes make the same choices on every run
+    app.insert_resource(BehaviorSeed(0));
+    app.init_resource::<BehaviorCooldowns>();
+    // Used by `BehaviorTreePlugin`, when tests add it
+    app.init_resource::<BehaviorHotReload>();
+    app.init_resource::<BehaviorSubtrees>();
     // Add the behaviors system to the app
-    app.add_systems((clear_behavior_started, complete_behavior, start_behavior).chain());
-    app.add_system(debug::run);
-    app.add_system(selector::run);
-    app.add_system(sequencer::run);
-    app.add_system(all::run);
-    app.add_system(any::run);
-    app.add_system(repeater::run);
-    app.add_system(inverter::run);
-    app.add_system(succeeder::run);
-    app.add_system(wait::run);
-    app.add_system(delay::run);
-    app.add_system(identity::run);
-    app.add_system(guard::run);
+    init_behavior_schedule(app);
+    app.edit_schedule(BehaviorSchedule, |schedule| {
+        schedule.add_system(debug::run);
+        schedule.add_system(selector::run);
+        schedule.add_system(sequencer::run);
+        schedule.add_system(all::run);
+        schedule.add_system(any::run);
+        schedule.add_system(parallel::run);
+        schedule.add_system(reactive_selector::run);
+        schedule.add_system(reactive_sequencer::run);
+        schedule.add_system(utility_selector::run);
+        schedule.add_system(random_selector::run);
+        schedule.add_system(shuffled_sequencer::run);
+        schedule.add_system(repeater::run);
+        schedule.add_system(inverter::run);
+        schedule.add_system(succeeder::run);
+        schedule.add_system(wait::run);
+        schedule.add_system(set_blackboard::run);
+        schedule.add_system(increment_blackboard::run);
+        schedule.add_system(compare_blackboard::run);
+        schedule.add_system(wait_for_event::run.before(emit_signal::run));
+        schedule.add_system(emit_signal::run);
+        schedule.add_system(delay::run);
+        schedule.add_system(identity::run);
+        schedule.add_system(guard::run);
+        schedule.add_system(timeout::run);
+        schedule.add_system(score::run);
+        schedule.add_system(retry::run);
+        schedule.add_system(cooldown::run);
+        schedule.add_system(async_action::poll_tasks);
+        schedule.add_system(async_action::cancel_tasks);
+        schedule.add_system(blackboard_changes);
+    });
     app.init_resource::<BehaviorTrace>();
     app
 }
 
+/// Attributes written by the editor, like node positions, are ignored
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
-pub struct TestBehaviorAttributes;
+pub struct TestBehaviorAttributes {}
 
 #[derive(Serialize, Deserialize, TypeUuid, Debug, Clone, Reflect, FromReflect, BehaviorFactory)]
 #[uuid = "3d6cc56a-542e-11ed-9abb-02a179e5df2b"]
@@ -44,14 +84,29 @@ pub enum TestBehavior {
     Sequencer(Sequencer),
     All(All),
     Any(Any),
+    Parallel(Parallel),
+    ReactiveSelector(ReactiveSelector),
+    ReactiveSequencer(ReactiveSequencer),
+    UtilitySelector(UtilitySelector),
+    RandomSelector(RandomSelector),
+    ShuffledSequencer(ShuffledSequencer),
     Repeater(Repeater),
     Inverter(Inverter),
     Succeeder(Succeeder),
     Wait(Wait),
+    SetBlackboard(SetBlackboard),
+    IncrementBlackboard(IncrementBlackboard),
+    CompareBlackboard(CompareBlackboard),
+    WaitForEvent(WaitForEvent),
+    EmitSignal(EmitSignal),
     Delay(Delay),
     Identity(Identity),
     Guard(Guard),
     Timeout(Timeout),
+    Score(Score),
+    Retry(Retry),
+    Cooldown(Cooldown),
+    Subtree(Subtree<TestBehavior>),
 }
 
 impl Default for TestBehavior {
@@ -61,35 +116,197 @@ impl Default for TestBehavior {
 }
 
 pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
-    // Load behavior tree from RON string
-    let document = ron::from_str::<Behavior<TestBehavior>>(behavior);
-    assert!(document.is_ok());
-    let document = document.unwrap();
-    // println!("Loaded behavior tree: \n{:#?}", ron::ser::to_string_pretty(&document, Default::default()).unwrap());
+    trace_behavior_with_timing(behavior, BehaviorTiming::stepped(TEST_TIMESTEP))
+}
+
+pub fn trace_behavior_with_timing(behavior: &str, timing: BehaviorTiming) -> BehaviorTrace {
+    trace_behavior_for_frames(behavior, timing, MAX_ITERS)
+}
+
+pub fn trace_behavior_for_frames(
+    behavior: &str,
+    timing: BehaviorTiming,
+    frames: usize,
+) -> BehaviorTrace {
+    trace_behavior_of::<TestBehavior>(behavior, timing, frames, |_| {})
+}
+
+/// Trace a behavior document of any behavior type. `setup` adds what the type
+/// needs to the test app, like its plugin, before the tree is spawned.
+pub fn trace_behavior_of<T>(
+    behavior: &str,
+    timing: BehaviorTiming,
+    frames: usize,
+    setup: impl FnOnce(&mut App),
+) -> BehaviorTrace
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    // Load behavior tree from RON string, with or without a blackboard schema
+    let BehaviorFile {
+        blackboard: schema,
+        root: document,
+    } = BehaviorFile::<T>::from_document(behavior)
+        .unwrap_or_else(|err| panic!("Failed to parse behavior document: {}", err));
 
     // Create app
     let mut app = App::new();
     app.add_plugin(bevy::time::TimePlugin::default());
     test_app(&mut app);
+    app.insert_resource(timing);
+    setup(&mut app);
+    spawn_test_tree(&mut app, &document, schema);
+
+    // Run app
+    let mut iters = 0;
+    while iters < frames {
+        iters += 1;
+        app.update();
+    }
+
+    // Get app trace
+    app.world.get_resource::<BehaviorTrace>().unwrap().clone()
+}
+
+/// Spawn a behavior tree with its cursor on the root, returns the tree entity
+pub fn spawn_test_tree<T: BehaviorFactory>(
+    app: &mut App,
+    behavior: &Behavior<T>,
+    schema: BlackboardSchema,
+) -> Entity {
+    // Create scripting scope
+    let mut script_ctx = BehaviorTree::<T>::create_script_context();
+    blackboard::apply_schema(&mut script_ctx, &schema);
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(script_ctx);
+
     let mut command_queue = CommandQueue::default();
     let mut commands = Commands::new(&mut command_queue, &app.world);
 
     // Spawn tree
-    let entity = commands.spawn_empty().id();
-    let root = BehaviorTree::insert_tree(entity, None, &mut commands, &document);
+    let entity = commands
+        .spawn((script_ctx_handle, Blackboard::new(schema)))
+        .id();
+    let root = BehaviorTree::insert_tree(entity, None, &mut commands, behavior);
     commands.entity(entity).add_child(root);
     commands.entity(root).insert(BehaviorCursor::Delegate);
 
     // Apply commands
     command_queue.apply(&mut app.world);
+    entity
+}
 
-    // Run app
-    let mut iters = 0;
-    while iters < MAX_ITERS {
-        iters += 1;
-        app.update();
+/// Run every `*.bht.ron` file in `dir` and its sub folders, and compare its trace
+/// with the sibling `*.trace` golden file, one trace entry per line. Golden
+/// files are written instead when the `BEHAVIOR_BLESS` env var is set.
+pub fn check_golden_traces(dir: impl AsRef<Path>) {
+    check_golden_traces_of::<TestBehavior>(dir, |_| {});
+}
+
+/// Check golden traces of behavior files of any behavior type, like
+/// `check_golden_traces`. `setup` adds what the type needs to each test app,
+/// see `trace_behavior_of`.
+pub fn check_golden_traces_of<T>(dir: impl AsRef<Path>, setup: impl Fn(&mut App))
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    let bless = std::env::var(BLESS_ENV).map_or(false, |bless| !bless.is_empty() && bless != "0");
+
+    let mut files = vec![];
+    find_behavior_files(dir.as_ref(), &mut files);
+    assert!(!files.is_empty(), "No behavior files in {:?}", dir.as_ref());
+
+    let mut failures = vec![];
+    for file in files {
+        let file_name = file.file_name().unwrap().to_string_lossy().to_string();
+        let golden_name = format!("{}.trace", file_name.trim_end_matches(".bht.ron"));
+        let golden = file.with_file_name(golden_name);
+
+        let document = std::fs::read_to_string(&file)
+            .unwrap_or_else(|err| panic!("Failed to read {}: {}", file.display(), err));
+        if let Err(err) = BehaviorFile::<T>::from_document(&document) {
+            failures.push(format!("{}\n  {}", file.display(), err));
+            continue;
+        }
+        let timing = BehaviorTiming::stepped(TEST_TIMESTEP);
+        let trace = trace_behavior_of::<T>(&document, timing, MAX_ITERS, &setup);
+
+        if bless {
+            let mut data = trace.join("\n");
+            data.push('\n');
+            std::fs::write(&golden, data)
+                .unwrap_or_else(|err| panic!("Failed to write {}: {}", golden.display(), err));
+            continue;
+        }
+
+        match std::fs::read_to_string(&golden) {
+            Ok(expected) => {
+                let expected = BehaviorTrace(
+                    expected
+                        .lines()
+                        .map(|line| line.trim())
+                        .filter(|line| !line.is_empty())
+                        .map(|line| line.to_string())
+                        .collect(),
+                );
+                if expected != trace {
+                    failures.push(format!(
+                        "{}\n{}",
+                        golden.display(),
+                        diff_traces(&expected, &trace)
+                    ));
+                }
+            }
+            Err(err) => {
+                failures.push(format!("{}\n  {}", golden.display(), err));
+            }
+        }
     }
 
-    // Get app trace
-    app.world.get_resource::<BehaviorTrace>().unwrap().clone()
+    assert!(
+        failures.is_empty(),
+        "Golden traces differ, set {}=1 to rewrite them\n\n{}",
+        BLESS_ENV,
+        failures.join("\n\n")
+    );
+}
+
+/// Find behavior files in a folder and its sub folders, sorted by path
+fn find_behavior_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir.display(), err))
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            find_behavior_files(&entry, files);
+        } else if entry.to_string_lossy().ends_with(".bht.ron") {
+            files.push(entry);
+        }
+    }
+}
+
+/// Line by line diff of two traces, `-` for expected and `+` for actual entries
+fn diff_traces(expected: &BehaviorTrace, actual: &BehaviorTrace) -> String {
+    let mut diff = vec![];
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => {
+                diff.push(format!("  {}", expected));
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    diff.push(format!("- {}", expected));
+                }
+                if let Some(actual) = actual {
+                    diff.push(format!("+ {}", actual));
+                }
+            }
+        }
+    }
+    diff.join("\n")
 }
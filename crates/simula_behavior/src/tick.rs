@@ -0,0 +1,294 @@
+use crate::{
+    clear_behavior_started, clear_behavior_stopped, complete_behavior, despawn_behavior,
+    resume_behavior, signal::BehaviorSignal, start_behavior, BehaviorSet,
+};
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+/// Schedule with all behavior systems. Behavior nodes are systems added to
+/// this schedule, with `add_behavior_system`, it runs once per tick.
+///
+/// Node systems added to the app with a plain `add_system` are deprecated.
+/// They run once per frame in `CoreSet::Update`, before the tick, so they keep
+/// working with one tick per frame and one hand-off per tick, like the default
+/// timing. They get out of sync with their tree in `Fixed` mode or with several
+/// hand-offs per tick.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BehaviorSchedule;
+
+/// Schedule run once at the end of every tick, after the cursor hand-offs of
+/// `BehaviorSchedule`, like to record trees
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BehaviorPostTickSchedule;
+
+/// Base sets of the behavior schedule. Behavior node systems go in `Update` by
+/// default, cursor hand-offs happen in `PostUpdate`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[system_set(base)]
+pub enum BehaviorBaseSet {
+    Update,
+    UpdateFlush,
+    PostUpdate,
+}
+
+/// How behavior trees tick
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub enum BehaviorTickMode {
+    /// Tick once per frame, the clock follows `Time`
+    #[default]
+    Frame,
+    /// Tick on a fixed timestep of real time, the clock advances by the timestep
+    /// on every tick. Trees might tick several times in a frame, up to
+    /// `BehaviorTiming::max_ticks_per_frame`, or not at all. The timestep must
+    /// be positive, trees don't tick otherwise.
+    Fixed(f64),
+    /// Tick once per frame, the clock advances by a fixed step on every tick, no
+    /// matter how long frames take. Deterministic, used by tests and replays.
+    Stepped(f64),
+}
+
+/// How far the cursor moves in a tick
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub enum BehaviorTraversal {
+    /// Hand off the cursor up to `BehaviorTiming::max_handoffs` times per tick
+    #[default]
+    Hops,
+    /// Keep handing off the cursor until every tree is blocked on a running
+    /// node, so instant nodes complete in the tick they start. Hand-offs are
+    /// still capped by `BehaviorTiming::max_handoffs`, so trees that never
+    /// block (like a repeater of instant actions) can't stall the app.
+    SingleFrame,
+}
+
+/// Hand-off passes per tick set by `BehaviorTiming::single_frame`
+pub const SINGLE_FRAME_MAX_HANDOFFS: usize = 64;
+
+/// Behavior tick configuration
+#[derive(Debug, Clone, Resource, Reflect, FromReflect)]
+#[reflect(Resource)]
+pub struct BehaviorTiming {
+    pub mode: BehaviorTickMode,
+    pub traversal: BehaviorTraversal,
+    /// Maximum number of cursor hand-offs per tick. Behavior systems run again
+    /// after every hand-off, until trees are blocked or the maximum is reached.
+    pub max_handoffs: usize,
+    /// Maximum number of ticks in a frame in `Fixed` mode. Ticks over it are
+    /// dropped, so a long frame doesn't make the next frames longer.
+    pub max_ticks_per_frame: usize,
+}
+
+impl Default for BehaviorTiming {
+    fn default() -> Self {
+        Self {
+            mode: BehaviorTickMode::Frame,
+            traversal: BehaviorTraversal::Hops,
+            max_handoffs: 1,
+            max_ticks_per_frame: 8,
+        }
+    }
+}
+
+impl BehaviorTiming {
+    /// Deterministic timing, ticks once per frame advancing the clock by `step`
+    pub fn stepped(step: f64) -> Self {
+        Self {
+            mode: BehaviorTickMode::Stepped(step),
+            ..default()
+        }
+    }
+
+    /// Tick on a fixed timestep of real time
+    pub fn fixed(timestep: f64) -> Self {
+        Self {
+            mode: BehaviorTickMode::Fixed(timestep),
+            ..default()
+        }
+    }
+
+    pub fn with_max_handoffs(mut self, max_handoffs: usize) -> Self {
+        self.max_handoffs = max_handoffs;
+        self
+    }
+
+    pub fn with_max_ticks_per_frame(mut self, max_ticks_per_frame: usize) -> Self {
+        self.max_ticks_per_frame = max_ticks_per_frame;
+        self
+    }
+
+    /// Traverse trees in a single tick, until they are blocked on running nodes,
+    /// with up to `SINGLE_FRAME_MAX_HANDOFFS` hand-offs. Use `with_max_handoffs`
+    /// after it to change the cap.
+    pub fn single_frame(mut self) -> Self {
+        self.traversal = BehaviorTraversal::SingleFrame;
+        self.max_handoffs = SINGLE_FRAME_MAX_HANDOFFS;
+        self
+    }
+
+    /// Cursor hand-off passes allowed in a tick
+    pub fn handoff_passes(&self) -> usize {
+        self.max_handoffs.max(1)
+    }
+}
+
+/// Clock used by behavior nodes, instead of `Time`
+#[derive(Debug, Default, Clone, Resource, Reflect, FromReflect)]
+#[reflect(Resource)]
+pub struct BehaviorClock {
+    elapsed: f64,
+    delta: f64,
+    ticks: u64,
+    accumulator: f64,
+    handoffs: usize,
+    /// Set while the fixed timestep is not positive, to report it once
+    invalid_timestep: bool,
+    /// Set while single frame traversals reach the hand-off cap, to report it once
+    unblocked: bool,
+}
+
+impl BehaviorClock {
+    /// Time elapsed since the first tick, in seconds
+    pub fn elapsed_seconds_f64(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Time elapsed since the previous tick, in seconds
+    pub fn delta_seconds_f64(&self) -> f64 {
+        self.delta
+    }
+
+    /// Number of ticks so far
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Record a cursor hand-off in the current tick
+    pub fn handoff(&mut self) {
+        self.handoffs += 1;
+    }
+
+    fn tick(&mut self, delta: f64) {
+        self.delta = delta;
+        self.elapsed += delta;
+        self.ticks += 1;
+    }
+}
+
+/// Add behavior node systems to the app
+pub trait BehaviorAppExt {
+    /// Add a behavior node system to `BehaviorSchedule`, it runs once per tick
+    /// and again after every cursor hand-off of the tick
+    fn add_behavior_system<M>(&mut self, system: impl IntoSystemAppConfig<M>) -> &mut Self;
+}
+
+impl BehaviorAppExt for App {
+    fn add_behavior_system<M>(&mut self, system: impl IntoSystemAppConfig<M>) -> &mut Self {
+        self.add_system(system.in_schedule(BehaviorSchedule))
+    }
+}
+
+/// Add the behavior schedule, its base sets and the cursor hand-off systems
+pub(crate) fn init_behavior_schedule(app: &mut App) {
+    let mut schedule = Schedule::new();
+    schedule
+        .set_default_base_set(BehaviorBaseSet::Update)
+        .configure_sets(
+            (
+                BehaviorBaseSet::Update,
+                BehaviorBaseSet::UpdateFlush,
+                BehaviorBaseSet::PostUpdate,
+            )
+                .chain(),
+        )
+        .configure_set(BehaviorSet::PostUpdate.in_base_set(BehaviorBaseSet::PostUpdate))
+        .add_system(apply_system_buffers.in_base_set(BehaviorBaseSet::UpdateFlush))
+        .add_systems(
+            (
+                resume_behavior,
+                clear_behavior_started,
+                clear_behavior_stopped,
+                despawn_behavior,
+                complete_behavior,
+                start_behavior,
+            )
+                .chain()
+                .in_set(BehaviorSet::PostUpdate),
+        );
+    app.add_schedule(BehaviorSchedule, schedule)
+        .add_schedule(BehaviorPostTickSchedule, Schedule::new())
+        .init_resource::<BehaviorTiming>()
+        .init_resource::<BehaviorClock>()
+        // Updated by `run_behavior_schedule` instead of in `First`, so signals
+        // don't expire in frames without ticks
+        .init_resource::<Events<BehaviorSignal>>()
+        .register_type::<BehaviorTiming>()
+        .register_type::<BehaviorTraversal>()
+        .register_type::<BehaviorClock>()
+        .add_system(run_behavior_schedule.in_base_set(CoreSet::PostUpdate));
+}
+
+/// Tick behavior trees, as configured by `BehaviorTiming`
+pub fn run_behavior_schedule(world: &mut World) {
+    let timing = world.resource::<BehaviorTiming>().clone();
+    let time = world.resource::<Time>();
+    let (elapsed, delta) = (time.elapsed_seconds_f64(), time.delta_seconds_f64());
+
+    // Find out how many ticks to run this frame
+    let mut clock = world.resource_mut::<BehaviorClock>();
+    let (ticks, step) = match timing.mode {
+        BehaviorTickMode::Frame => {
+            // Follow wall-clock time, the tick adds the delta back
+            clock.elapsed = elapsed - delta;
+            (1, delta)
+        }
+        BehaviorTickMode::Fixed(timestep) if timestep > 0.0 && timestep.is_finite() => {
+            clock.invalid_timestep = false;
+            clock.accumulator += delta;
+            let ticks = (clock.accumulator / timestep).floor();
+            clock.accumulator -= ticks * timestep;
+            // Drop the ticks over the limit, instead of catching up with them
+            // in the next frames
+            let ticks = (ticks as usize).min(timing.max_ticks_per_frame.max(1));
+            (ticks, timestep)
+        }
+        BehaviorTickMode::Fixed(timestep) => {
+            if !clock.invalid_timestep {
+                error!("Behavior fixed timestep must be positive, got {}", timestep);
+                clock.invalid_timestep = true;
+            }
+            clock.accumulator = 0.0;
+            (0, 0.0)
+        }
+        BehaviorTickMode::Stepped(step) => (1, step),
+    };
+
+    let passes = timing.handoff_passes();
+    for _ in 0..ticks {
+        world.resource_mut::<BehaviorClock>().tick(step);
+        let mut blocked = false;
+        for _ in 0..passes {
+            world.resource_mut::<BehaviorClock>().handoffs = 0;
+            world.run_schedule(BehaviorSchedule);
+            // Trees are blocked, no need to run again
+            if world.resource::<BehaviorClock>().handoffs == 0 {
+                blocked = true;
+                break;
+            }
+        }
+        // Report trees that don't block once, until they block again
+        let unblocked = !blocked && timing.traversal == BehaviorTraversal::SingleFrame;
+        let mut clock = world.resource_mut::<BehaviorClock>();
+        if unblocked && !clock.unblocked {
+            warn!(
+                "Behavior trees still handing off after {} passes, a tree might never block",
+                passes
+            );
+        }
+        clock.unblocked = unblocked;
+        world.run_schedule(BehaviorPostTickSchedule);
+    }
+
+    // Signals are kept for two frames with ticks, instead of two frames, so
+    // trees can read them no matter how many frames they skip
+    if ticks > 0 {
+        world.resource_mut::<Events<BehaviorSignal>>().update();
+    }
+}
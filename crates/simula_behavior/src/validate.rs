@@ -0,0 +1,190 @@
+use crate::{asset::subtree_asset, Behavior, BehaviorFactory, BehaviorTree, BehaviorType};
+use bevy::utils::HashMap;
+use simula_script::ScriptContext;
+use std::{borrow::Cow, fmt, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Node has the wrong number of children for its type
+    ChildCount,
+    /// Script in a property doesn't compile
+    Script,
+    /// Subtree asset doesn't exist
+    MissingSubtree,
+    /// More than one node with the same name
+    DuplicateName,
+}
+
+/// A problem found in a behavior tree, before it is spawned
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    /// Child indices from the root to the node, empty for the root
+    pub path: Vec<usize>,
+    pub name: Cow<'static, str>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let path = self
+            .path
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        write!(
+            f,
+            "{}: [{}] {}: {}",
+            severity, path, self.name, self.message
+        )
+    }
+}
+
+/// Check a behavior tree before it is spawned, looking up subtree assets in
+/// `asset_folder`, the folder of the asset server
+pub fn validate<T: BehaviorFactory>(
+    behavior: &Behavior<T>,
+    asset_folder: &Path,
+) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        asset_folder,
+        script_ctx: BehaviorTree::<T>::create_script_context(),
+        names: HashMap::default(),
+        diagnostics: vec![],
+    };
+    validator.node(behavior, &mut vec![]);
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    asset_folder: &'a Path,
+    script_ctx: ScriptContext,
+    names: HashMap<String, Vec<usize>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn node<T: BehaviorFactory>(&mut self, behavior: &Behavior<T>, path: &mut Vec<usize>) {
+        let data = behavior.data();
+        let children = behavior.nodes().len();
+        let child_count = match data.typ() {
+            BehaviorType::Action if children > 0 => Some((
+                Severity::Error,
+                format!("Action node can't have children, found {}", children),
+            )),
+            BehaviorType::Composite if children == 0 => Some((
+                Severity::Warning,
+                "Composite node has no children".to_string(),
+            )),
+            BehaviorType::Decorator if children != 1 => Some((
+                Severity::Error,
+                format!("Decorator node requires one child, found {}", children),
+            )),
+            BehaviorType::Subtree if children > 1 => Some((
+                Severity::Error,
+                format!(
+                    "Subtree node can only have its inlined asset as child, found {} children",
+                    children
+                ),
+            )),
+            _ => None,
+        };
+        if let Some((severity, message)) = child_count {
+            self.push(
+                behavior,
+                path,
+                severity,
+                DiagnosticKind::ChildCount,
+                message,
+            );
+        }
+
+        // Compile every script in the node properties
+        for eval in data.scripts() {
+            if let Err(err) = self.script_ctx.engine.compile(eval) {
+                let message = format!("Script `{}` doesn't compile: {}", eval, err);
+                self.push(
+                    behavior,
+                    path,
+                    Severity::Error,
+                    DiagnosticKind::Script,
+                    message,
+                );
+            }
+        }
+
+        // Subtree assets must exist
+        if let Some(asset) = subtree_asset(data) {
+            if !self.asset_folder.join(asset).is_file() {
+                let message = format!("Subtree asset `{}` not found", asset);
+                self.push(
+                    behavior,
+                    path,
+                    Severity::Error,
+                    DiagnosticKind::MissingSubtree,
+                    message,
+                );
+            }
+        }
+
+        // Names should be unique, to tell nodes apart in traces and the inspector
+        if let Some(first) = self.names.get(behavior.name()) {
+            let first = first
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<_>>()
+                .join(".");
+            let message = format!("Name is already used by node [{}]", first);
+            self.push(
+                behavior,
+                path,
+                Severity::Warning,
+                DiagnosticKind::DuplicateName,
+                message,
+            );
+        } else {
+            self.names.insert(behavior.name().to_string(), path.clone());
+        }
+
+        for (index, child) in behavior.nodes().iter().enumerate() {
+            path.push(index);
+            self.node(child, path);
+            path.pop();
+        }
+    }
+
+    fn push<T: BehaviorFactory>(
+        &mut self,
+        behavior: &Behavior<T>,
+        path: &[usize],
+        severity: Severity,
+        kind: DiagnosticKind,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            kind,
+            path: path.to_vec(),
+            name: behavior.name().to_string().into(),
+            message,
+        });
+    }
+}
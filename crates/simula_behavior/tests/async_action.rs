@@ -0,0 +1,216 @@
+use bevy::{
+    ecs::system::EntityCommands, prelude::*, reflect::TypeUuid, tasks::futures_lite::future,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_behavior_macro::{BehaviorFactory, BehaviorSpec, BehaviorUI};
+use simula_script::ScriptContext;
+
+/// Stand-in for a planner, completes at once unless it runs forever
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, icon = "⚙", desc = "Compute a result in the background")]
+pub struct Compute {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub fail: BehaviorPropGeneric<bool>,
+    #[serde(default)]
+    pub forever: bool,
+}
+
+impl AsyncAction for Compute {
+    fn work(
+        &mut self,
+        node: &BehaviorNode,
+        scripts: &mut ScriptQueries,
+    ) -> Option<Result<BoxedFuture<'static, Result<(), String>>, String>> {
+        // Eval props again every time the work starts
+        self.fail.value = BehaviorPropValue::None;
+        if let Err(err) = self.fail.fetch(node, scripts)? {
+            return Some(Err(err));
+        }
+        let BehaviorPropValue::Some(fail) = self.fail.value else {
+            return None;
+        };
+        let forever = self.forever;
+        Some(Ok(Box::pin(async move {
+            if forever {
+                future::pending::<()>().await;
+            }
+            if fail {
+                Err("No result".to_string())
+            } else {
+                Ok(())
+            }
+        })))
+    }
+}
+
+#[derive(Serialize, Deserialize, TypeUuid, Debug, Clone, Reflect, FromReflect, BehaviorFactory)]
+#[uuid = "364d8a76-2051-4e87-b835-af1dfdb3c3c1"]
+#[BehaviorAttributes(TestBehaviorAttributes)]
+pub enum ComputeBehavior {
+    Sequencer(Sequencer),
+    Timeout(Timeout),
+    Compute(Compute),
+}
+
+impl Default for ComputeBehavior {
+    fn default() -> Self {
+        Self::Compute(Compute::default())
+    }
+}
+
+// Tasks run on other threads, wait for the ones that complete so each run
+// takes the same number of updates
+fn finish_tasks(mut tasks: Query<(&mut BehaviorTask, &Compute)>) {
+    for (mut task, compute) in &mut tasks {
+        if !compute.forever {
+            task.block_on();
+        }
+    }
+}
+
+// Run a behavior for a number of frames, restored from a save if any
+fn trace_compute(behavior: &str, save: Option<BehaviorTreeSave>) -> (App, Entity, BehaviorTrace) {
+    let mut app = App::new();
+    app.add_plugin(bevy::core::TaskPoolPlugin::default())
+        .add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<ComputeBehavior>::default())
+        .edit_schedule(BehaviorSchedule, |schedule| {
+            schedule.add_system(async_action::run::<Compute>);
+            schedule.add_system(finish_tasks.before(async_action::poll_tasks));
+        });
+
+    let behavior = ron::from_str::<Behavior<ComputeBehavior>>(behavior).unwrap();
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<ComputeBehavior>>>()
+        .add(BehaviorAsset {
+            behavior,
+            blackboard: default(),
+            file_name: None,
+        });
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(BehaviorTree::<ComputeBehavior>::create_script_context());
+    let mut tree = app.world.spawn((
+        behavior_handle,
+        script_ctx_handle,
+        BehaviorTree::<ComputeBehavior>::default(),
+        BehaviorTreeReset::<ComputeBehavior>::default(),
+    ));
+    if let Some(save) = save {
+        tree.insert(save);
+    }
+    let tree = tree.id();
+
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    (app, tree, trace)
+}
+
+#[test]
+fn async_action_result() {
+    let behavior = r#"
+    (
+        "Plan and query",
+        Sequencer(()),
+        [
+            ("Plan path", Compute(())),
+            ("Query database", Compute((fail: (prop: Eval(eval: "1 > 0"))))),
+        ]
+    )
+    "#;
+    let (_, _, trace) = trace_compute(behavior, None);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Plan and query",
+        "[2] STARTED Plan path",
+        "[2] SUCCESS Plan path",
+        "[3] STARTED Query database",
+        "[3] FAILURE Query database",
+        "[1] FAILURE Plan and query",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn async_action_cancelled() {
+    let behavior = r#"
+    (
+        "Plan with a time limit",
+        Timeout((duration: (prop: Value(0.1)))),
+        [
+            ("Plan forever", Compute((forever: true))),
+        ]
+    )
+    "#;
+    let (mut app, _, trace) = trace_compute(behavior, None);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Plan with a time limit",
+        "[2] STARTED Plan forever",
+        "[1] FAILURE Plan with a time limit",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+
+    // The stopped node dropped its task
+    let tasks = app.world.query::<&BehaviorTask>().iter(&app.world).count();
+    assert_eq!(tasks, 0);
+}
+
+#[test]
+fn async_action_restored() {
+    let behavior = r#"
+    (
+        "Plan and act",
+        Sequencer(()),
+        [
+            ("Plan", Compute((forever: true))),
+            ("Act", Compute(())),
+        ]
+    )
+    "#;
+    let (app, tree, trace) = trace_compute(behavior, None);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Plan and act", "[2] STARTED Plan"]);
+    assert_eq!(&trace, &expected_trace);
+    let save = BehaviorTreeSave::from_world::<ComputeBehavior>(&app.world, tree).unwrap();
+
+    // Tasks are not saved, the running node spawns its work again. Saved nodes
+    // match by name, so this time the plan completes.
+    let behavior = behavior.replace("(forever: true)", "()");
+    let (_, _, trace) = trace_compute(&behavior, Some(save));
+    println!("{:#?}", trace);
+    let trace: Vec<&str> = trace
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1)
+        .collect();
+    assert_eq!(
+        trace,
+        vec![
+            "SUCCESS Plan",
+            "STARTED Act",
+            "SUCCESS Act",
+            "SUCCESS Plan and act",
+        ]
+    );
+}
@@ -0,0 +1,169 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+use simula_behavior::{blackboard, prelude::*, test::*};
+use simula_script::{script, ScriptContext};
+
+const DOCUMENT: &str = r#"
+(
+    blackboard: [
+        (key: "health", default: Int(100)),
+        (key: "alert", default: Bool(false)),
+    ],
+    root: ("Do a thing", Debug(())),
+)
+"#;
+
+fn blackboard_app() -> (App, Entity) {
+    let document = BehaviorFile::<TestBehavior>::from_document(DOCUMENT).unwrap();
+    assert_eq!(document.root.name(), "Do a thing");
+    assert_eq!(document.blackboard.len(), 2);
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+
+    let mut script_ctx = BehaviorTree::<TestBehavior>::create_script_context();
+    blackboard::apply_schema(&mut script_ctx, &document.blackboard);
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(script_ctx);
+    let tree = app
+        .world
+        .spawn((script_ctx_handle, Blackboard::new(document.blackboard)))
+        .id();
+    (app, tree)
+}
+
+#[test]
+fn blackboard_legacy_document() {
+    let document = BehaviorFile::<TestBehavior>::from_document(r#"("Do a thing", Debug(()))"#);
+    let document = document.unwrap();
+    assert_eq!(document.root.name(), "Do a thing");
+    assert!(document.blackboard.is_empty());
+}
+
+#[test]
+fn blackboard_typed_access() {
+    let (mut app, tree) = blackboard_app();
+    let mut state: SystemState<Blackboards> = SystemState::new(&mut app.world);
+    let mut blackboards = state.get_mut(&mut app.world);
+
+    assert_eq!(blackboards.get::<i64>(tree, "health"), Some(100));
+    assert_eq!(blackboards.get::<bool>(tree, "alert"), Some(false));
+    assert_eq!(blackboards.get::<bool>(tree, "health"), None);
+    assert_eq!(blackboards.get::<i64>(tree, "state"), Some(0));
+
+    assert!(blackboards.set(tree, "health", 50i64).is_ok());
+    assert!(blackboards.set(tree, "health", true).is_err());
+    assert_eq!(blackboards.get::<i64>(tree, "health"), Some(50));
+
+    let snapshot = blackboards.snapshot(tree).unwrap();
+    assert!(blackboards.set(tree, "health", 10i64).is_ok());
+    assert!(blackboards.restore(tree, &snapshot).is_ok());
+    assert_eq!(blackboards.get::<i64>(tree, "health"), Some(50));
+}
+
+#[test]
+fn blackboard_change_events() {
+    let (mut app, tree) = blackboard_app();
+    app.update();
+
+    let mut state: SystemState<Blackboards> = SystemState::new(&mut app.world);
+    let mut blackboards = state.get_mut(&mut app.world);
+    assert!(blackboards.set(tree, "alert", true).is_ok());
+    app.update();
+
+    let events = app.world.resource::<Events<BlackboardChanged>>();
+    let changes: Vec<&BlackboardChanged> = events.get_reader().iter(events).collect();
+    assert_eq!(
+        changes,
+        vec![&BlackboardChanged {
+            tree,
+            key: "alert".into(),
+            value: Some(BlackboardValue::Bool(true)),
+        }]
+    );
+}
+
+#[test]
+fn blackboard_removed_events() {
+    let (mut app, tree) = blackboard_app();
+    app.update();
+
+    let mut state: SystemState<Blackboards> = SystemState::new(&mut app.world);
+    let mut blackboards = state.get_mut(&mut app.world);
+    let snapshot = blackboards.snapshot(tree).unwrap();
+    let snapshot = BlackboardSnapshot(
+        snapshot
+            .0
+            .into_iter()
+            .filter(|(key, _)| key != "alert")
+            .collect(),
+    );
+    assert!(blackboards.restore(tree, &snapshot).is_ok());
+    app.update();
+
+    let events = app.world.resource::<Events<BlackboardChanged>>();
+    let changes: Vec<&BlackboardChanged> = events.get_reader().iter(events).collect();
+    assert_eq!(
+        changes,
+        vec![&BlackboardChanged {
+            tree,
+            key: "alert".into(),
+            value: None,
+        }]
+    );
+}
+
+#[test]
+fn blackboard_restore_keeps_untyped_values() {
+    let (mut app, tree) = blackboard_app();
+    let script_ctx_handle = app
+        .world
+        .get::<Handle<ScriptContext>>(tree)
+        .unwrap()
+        .clone();
+    {
+        let mut script_ctxs = app.world.resource_mut::<Assets<ScriptContext>>();
+        let script_ctx = script_ctxs.get_mut(&script_ctx_handle).unwrap();
+        let mut map = script_ctx
+            .scope
+            .get_value::<script::Map>(blackboard::BLACKBOARD)
+            .unwrap();
+        map.insert("path".into(), script::Dynamic::from(script::Array::new()));
+        script_ctx.scope.set_value(blackboard::BLACKBOARD, map);
+    }
+
+    let mut state: SystemState<Blackboards> = SystemState::new(&mut app.world);
+    let mut blackboards = state.get_mut(&mut app.world);
+    let snapshot = blackboards.snapshot(tree).unwrap();
+    assert!(snapshot.0.iter().all(|(key, _)| key != "path"));
+    assert!(blackboards.set(tree, "target", 3i64).is_ok());
+    assert!(blackboards.restore(tree, &snapshot).is_ok());
+    assert_eq!(blackboards.get_value(tree, "target"), None);
+    assert_eq!(blackboards.get::<i64>(tree, "health"), Some(100));
+
+    let script_ctxs = app.world.resource::<Assets<ScriptContext>>();
+    let map = script_ctxs
+        .get(&script_ctx_handle)
+        .unwrap()
+        .scope
+        .get_value::<script::Map>(blackboard::BLACKBOARD)
+        .unwrap();
+    assert!(map.get("path").unwrap().is::<script::Array>());
+}
+
+#[test]
+fn blackboard_schema_document_error() {
+    // The schema document is missing its root, the error is about the schema
+    // format and not about the legacy format
+    let document = r#"
+    (
+        blackboard: [
+            (key: "health", default: Int(100)),
+        ],
+    )
+    "#;
+    let err = BehaviorFile::<TestBehavior>::from_document(document).unwrap_err();
+    assert!(err.to_string().contains("root"), "{}", err);
+}
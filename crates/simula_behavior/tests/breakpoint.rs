@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn run_frames(app: &mut App, frames: usize) -> BehaviorTrace {
+    for _ in 0..frames {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+fn find_node(app: &mut App, name: &str) -> Entity {
+    app.world
+        .query_filtered::<(Entity, &Name), With<BehaviorNode>>()
+        .iter(&app.world)
+        .find(|(_, node_name)| node_name.as_str() == name)
+        .map(|(entity, _)| entity)
+        .unwrap()
+}
+
+#[test]
+fn breakpoint_pause_and_step() {
+    let behavior = r#"
+    (
+        "Sequencer of a few actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+        ],
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP));
+    let tree = spawn_test_tree(&mut app, &behavior, default());
+    let node = find_node(&mut app, "Do action 1");
+    app.world.entity_mut(node).insert(BehaviorBreakpoint);
+
+    // Tree stays paused at the breakpoint
+    let trace = run_frames(&mut app, 20);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of a few actions",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] PAUSED Do action 1",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+    assert!(app.world.get::<BehaviorPaused>(tree).is_some());
+
+    // Step over to the next node
+    app.world.entity_mut(tree).insert(BehaviorResume::StepOver);
+    let trace = run_frames(&mut app, 20);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of a few actions",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] PAUSED Do action 1",
+        "[3] SUCCESS Do action 1",
+        "[4] STARTED Do action 2",
+        "[4] PAUSED Do action 2",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+
+    // Continue until the end
+    app.world.entity_mut(tree).insert(BehaviorResume::Continue);
+    let trace = run_frames(&mut app, 20);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of a few actions",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] PAUSED Do action 1",
+        "[3] SUCCESS Do action 1",
+        "[4] STARTED Do action 2",
+        "[4] PAUSED Do action 2",
+        "[4] SUCCESS Do action 2",
+        "[1] SUCCESS Sequencer of a few actions",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+    assert!(app.world.get::<BehaviorPaused>(tree).is_none());
+}
+
+#[test]
+fn step_into_pauses_at_child() {
+    let behavior = r#"
+    (
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+        ],
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP));
+    let tree = spawn_test_tree(&mut app, &behavior, default());
+    let node = find_node(&mut app, "Sequencer of actions");
+    app.world.entity_mut(node).insert(BehaviorBreakpoint);
+    run_frames(&mut app, 5);
+
+    // Step into pauses at the first child of the paused node
+    app.world.entity_mut(tree).insert(BehaviorResume::StepInto);
+    let trace = run_frames(&mut app, 20);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of actions",
+        "[1] PAUSED Sequencer of actions",
+        "[2] STARTED Do action 0",
+        "[2] PAUSED Do action 0",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn step_over_skips_children() {
+    let behavior = r#"
+    (
+        "Sequencer of sequence",
+        Sequencer(()),
+        [
+            (
+                "Nested sequence",
+                Sequencer(()),
+                [
+                    ("Nested action 0", Debug(())),
+                    ("Nested action 1", Debug(())),
+                ],
+            ),
+            ("Do action", Debug(())),
+        ],
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP));
+    let tree = spawn_test_tree(&mut app, &behavior, default());
+    let node = find_node(&mut app, "Nested sequence");
+    app.world.entity_mut(node).insert(BehaviorBreakpoint);
+    run_frames(&mut app, 5);
+
+    // Step over runs the children of the paused node without pausing
+    app.world.entity_mut(tree).insert(BehaviorResume::StepOver);
+    let trace = run_frames(&mut app, 20);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of sequence",
+        "[2] STARTED Nested sequence",
+        "[2] PAUSED Nested sequence",
+        "[3] STARTED Nested action 0",
+        "[3] SUCCESS Nested action 0",
+        "[4] STARTED Nested action 1",
+        "[4] SUCCESS Nested action 1",
+        "[2] SUCCESS Nested sequence",
+        "[5] STARTED Do action",
+        "[5] PAUSED Do action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+    assert!(app.world.get::<BehaviorPaused>(tree).is_some());
+}
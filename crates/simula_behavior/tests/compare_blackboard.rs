@@ -0,0 +1,46 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn compare_blackboard_success() {
+    let behavior = r#"
+    (
+        "Check state",
+        CompareBlackboard((key: (prop: Value("state")), op: LessOrEqual, value: (prop: Value(Float(0.5))))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Check state", "[1] SUCCESS Check state"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn compare_blackboard_failure() {
+    let behavior = r#"
+    (
+        "Check state",
+        CompareBlackboard((key: (prop: Value("state")), op: Greater, value: (prop: Value(Int(0))))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Check state", "[1] FAILURE Check state"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn compare_blackboard_missing_key() {
+    let behavior = r#"
+    (
+        "Check missing",
+        CompareBlackboard((key: (prop: Value("missing")), value: (prop: Value(Int(0))))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Check missing", "[1] FAILURE Check missing"]);
+    assert_eq!(&trace, &expected_trace);
+}
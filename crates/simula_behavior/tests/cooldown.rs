@@ -0,0 +1,111 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn cooldown_fails_while_cooling_down() {
+    let behavior = r#"
+    (
+        "Do a few times",
+        Repeater((repeat:Times(3))),
+        [
+            ("Not too often", Cooldown((duration: (prop: Value(1.0)))), [
+                ("Do an action", Debug(())),
+            ]),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Do a few times",
+        "[2] STARTED Not too often",
+        "[3] STARTED Do an action",
+        "[3] SUCCESS Do an action",
+        "[2] SUCCESS Not too often",
+        "[1] STARTED Do a few times",
+        "[2] STARTED Not too often",
+        "[2] FAILURE Not too often",
+        "[1] STARTED Do a few times",
+        "[2] STARTED Not too often",
+        "[2] FAILURE Not too often",
+        "[1] SUCCESS Do a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn cooldown_after_duration() {
+    // The same cooldown fails right after its child completes, and runs the
+    // child again once the duration has passed
+    let behavior = r#"
+    (
+        "Do a few times",
+        Repeater((repeat:Times(3))),
+        [
+            ("Try", Selector(()), [
+                ("Not too often", Cooldown((duration: (prop: Value(0.5)))), [
+                    ("Do an action", Debug(())),
+                ]),
+                ("Wait a bit", Wait((duration: (prop: Value(1.0))))),
+            ]),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Do a few times",
+        "[2] STARTED Try",
+        "[3] STARTED Not too often",
+        "[4] STARTED Do an action",
+        "[4] SUCCESS Do an action",
+        "[3] SUCCESS Not too often",
+        "[2] SUCCESS Try",
+        "[1] STARTED Do a few times",
+        "[2] STARTED Try",
+        "[3] STARTED Not too often",
+        "[3] FAILURE Not too often",
+        "[5] STARTED Wait a bit",
+        "[5] SUCCESS Wait a bit",
+        "[2] SUCCESS Try",
+        "[1] STARTED Do a few times",
+        "[2] STARTED Try",
+        "[3] STARTED Not too often",
+        "[4] STARTED Do an action",
+        "[4] SUCCESS Do an action",
+        "[3] SUCCESS Not too often",
+        "[2] SUCCESS Try",
+        "[1] SUCCESS Do a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn cooldown_shared_key() {
+    let behavior = r#"
+    (
+        "Talk twice",
+        Sequencer(()),
+        [
+            ("Greet", Cooldown((duration: (prop: Value(1.0)), key: (prop: Value("chatter")))), [
+                ("Say hello", Debug(())),
+            ]),
+            ("Joke", Cooldown((duration: (prop: Value(1.0)), key: (prop: Value("chatter")))), [
+                ("Tell a joke", Debug(())),
+            ]),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Talk twice",
+        "[2] STARTED Greet",
+        "[3] STARTED Say hello",
+        "[3] SUCCESS Say hello",
+        "[2] SUCCESS Greet",
+        "[4] STARTED Joke",
+        "[4] FAILURE Joke",
+        "[1] FAILURE Talk twice",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,102 @@
+use bevy::{ecs::entity::EntityMap, prelude::*};
+use serde::{Deserialize, Serialize};
+use simula_behavior::prelude::*;
+use simula_behavior_macro::{BehaviorSpec, BehaviorUI};
+
+/// Walk to a point
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, name = "Walk To", icon = "🚶")]
+pub struct WalkTo {
+    #[serde(default)]
+    #[behavior(prop)]
+    pub speed: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    #[behavior(property)]
+    pub stop_distance: f64,
+    #[serde(skip)]
+    #[behavior(readonly)]
+    pub distance: f64,
+}
+
+/// Follow a target
+#[derive(
+    Debug,
+    Default,
+    Component,
+    Reflect,
+    FromReflect,
+    Clone,
+    Deserialize,
+    Serialize,
+    BehaviorSpec,
+    BehaviorUI,
+)]
+#[behavior(action, icon = "🐾")]
+pub struct Follow {
+    #[serde(default)]
+    #[behavior(property)]
+    pub distance: f64,
+    #[serde(skip)]
+    #[behavior(readonly, save_time)]
+    pub start: f64,
+    #[serde(skip)]
+    #[behavior(save)]
+    pub steps: u64,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    #[behavior(save)]
+    pub target: Option<Entity>,
+}
+
+#[test]
+fn derive_behavior_spec() {
+    assert_eq!(WalkTo::TYPE, BehaviorType::Action);
+    assert_eq!(WalkTo::NAME, "Walk To");
+    assert_eq!(WalkTo::ICON, "🚶");
+    assert_eq!(WalkTo::DESC, "Walk to a point");
+}
+
+#[test]
+fn derive_behavior_spec_defaults() {
+    assert_eq!(Inverter::TYPE, BehaviorType::Decorator);
+    assert_eq!(Inverter::NAME, "Inverter");
+    assert_eq!(All::TYPE, BehaviorType::Composite);
+    assert_eq!(All::NAME, "All");
+}
+
+#[test]
+fn derive_behavior_spec_save_state() {
+    let clock = BehaviorClock::default();
+    let target = Entity::from_raw(7);
+    let follow = Follow {
+        distance: 2.0,
+        start: 1.5,
+        steps: 3,
+        target: Some(target),
+    };
+    let state = follow.save_state(&clock).unwrap();
+
+    // Only saved fields are restored, saved entities are mapped
+    let mut entity_map = EntityMap::default();
+    entity_map.insert(target, Entity::from_raw(8));
+    let mut restored = Follow::default();
+    restored.restore_state(&state, &clock, &entity_map);
+    assert_eq!(restored.distance, 0.0);
+    assert_eq!(restored.start, 1.5);
+    assert_eq!(restored.steps, 3);
+    assert_eq!(restored.target, Some(Entity::from_raw(8)));
+
+    // Nodes without saved fields save nothing
+    assert!(WalkTo::default().save_state(&clock).is_none());
+}
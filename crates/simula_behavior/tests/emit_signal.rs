@@ -0,0 +1,26 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn emit_signal_global() {
+    let behavior = r#"
+    (
+        "Wait for all",
+        All(()),
+        [
+            ("Wait for target", WaitForEvent((signal: (prop: Value("target lost"))))),
+            ("Lose target", EmitSignal((signal: (prop: Value("target lost")), scope: Global))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Wait for all",
+        "[2] STARTED Wait for target",
+        "[3] STARTED Lose target",
+        "[3] SUCCESS Lose target",
+        "[2] SUCCESS Wait for target",
+        "[1] SUCCESS Wait for all",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,6 @@
+use simula_behavior::test::*;
+
+#[test]
+fn golden_traces() {
+    check_golden_traces(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"));
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::ScriptContext;
+
+const BEFORE: &str = r#"
+(
+    "Patrol",
+    Sequencer(()),
+    [
+        ("Go to A", Debug(())),
+        ("Wait at A", Debug((duration: (prop: Value(10.0))))),
+    ],
+)
+"#;
+
+const AFTER: &str = r#"
+(
+    "Patrol",
+    Sequencer(()),
+    [
+        ("Go to A", Debug(())),
+        ("Wait at A", Debug(())),
+        ("Go to B", Debug(())),
+    ],
+)
+"#;
+
+// Run BEFORE for a few frames, change the asset to AFTER, and return the trace
+// without node indices, as nodes are spawned again
+fn reload_behavior(hot_reload: BehaviorHotReload) -> Vec<String> {
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .insert_resource(hot_reload)
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default());
+
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(BEFORE).unwrap();
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .add(BehaviorAsset {
+            behavior,
+            blackboard: default(),
+            file_name: None,
+        });
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(BehaviorTree::<TestBehavior>::create_script_context());
+    app.world.spawn((
+        behavior_handle.clone(),
+        script_ctx_handle,
+        BehaviorTree::<TestBehavior>::default(),
+        BehaviorTreeReset::<TestBehavior>::default(),
+    ));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(AFTER).unwrap();
+    app.world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .get_mut(&behavior_handle)
+        .unwrap()
+        .behavior = behavior;
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    app.world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1.to_string())
+        .collect()
+}
+
+#[test]
+fn hot_reload_restart() {
+    let trace = reload_behavior(BehaviorHotReload::Restart);
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+            // Reloaded
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+            "SUCCESS Wait at A",
+            "STARTED Go to B",
+            "SUCCESS Go to B",
+            "SUCCESS Patrol",
+        ]
+    );
+}
+
+#[test]
+fn hot_reload_preserve_cursor() {
+    let trace = reload_behavior(BehaviorHotReload::PreserveCursor);
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+            // Reloaded, the node with the cursor starts again
+            "STARTED Wait at A",
+            "SUCCESS Wait at A",
+            "STARTED Go to B",
+            "SUCCESS Go to B",
+            "SUCCESS Patrol",
+        ]
+    );
+}
+
+#[test]
+fn hot_reload_disabled() {
+    let trace = reload_behavior(BehaviorHotReload::Disabled);
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+        ]
+    );
+}
+
+#[derive(Default, Resource)]
+struct Stopped(Vec<String>);
+
+// Nodes told to release what they hold
+fn on_stop(mut stopped: ResMut<Stopped>, nodes: Query<&Name, With<BehaviorStopped>>) {
+    for name in &nodes {
+        stopped.0.push(name.to_string());
+    }
+}
+
+#[test]
+fn hot_reload_document() {
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .init_resource::<Stopped>()
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default())
+        .edit_schedule(BehaviorSchedule, |schedule| {
+            schedule.add_system(on_stop);
+        });
+
+    // Tree is loaded from a document, like a `.bht.ron` file
+    let document_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorDocument>>()
+        .add(BehaviorDocument::new(BEFORE));
+    let tree = app
+        .world
+        .spawn((
+            document_handle.clone(),
+            BehaviorTree::<TestBehavior>::default(),
+            BehaviorTreeReset::<TestBehavior>::default(),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(app.world.get::<BehaviorTreeSource>(tree).is_some());
+    let old_root = app.world.get::<Children>(tree).unwrap()[0];
+
+    // Changing the document rebuilds the tree, as if the file was saved
+    *app.world
+        .resource_mut::<Assets<BehaviorDocument>>()
+        .get_mut(&document_handle)
+        .unwrap() = BehaviorDocument::new(AFTER);
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let trace: Vec<String> = app
+        .world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1.to_string())
+        .collect();
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+            // Reloaded
+            "STARTED Patrol",
+            "STARTED Go to A",
+            "SUCCESS Go to A",
+            "STARTED Wait at A",
+            "SUCCESS Wait at A",
+            "STARTED Go to B",
+            "SUCCESS Go to B",
+            "SUCCESS Patrol",
+        ]
+    );
+
+    // Running nodes of the old tree were stopped, then despawned
+    let mut stopped = app.world.resource::<Stopped>().0.clone();
+    stopped.sort();
+    assert_eq!(stopped, vec!["Patrol", "Wait at A"]);
+    assert!(app.world.get_entity(old_root).is_none());
+}
@@ -0,0 +1,54 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn increment_blackboard_count() {
+    let behavior = r#"
+    (
+        "Count and check",
+        Sequencer(()),
+        [
+            ("Count one", IncrementBlackboard((key: (prop: Value("count"))))),
+            ("Count two", IncrementBlackboard((key: (prop: Value("count")), amount: (prop: Value(Int(2)))))),
+            ("Check count", CompareBlackboard((key: (prop: Value("count")), value: (prop: Value(Int(3)))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Count and check",
+        "[2] STARTED Count one",
+        "[2] SUCCESS Count one",
+        "[3] STARTED Count two",
+        "[3] SUCCESS Count two",
+        "[4] STARTED Check count",
+        "[4] SUCCESS Check count",
+        "[1] SUCCESS Count and check",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn increment_blackboard_not_a_number() {
+    let behavior = r#"
+    (
+        "Count a name",
+        Sequencer(()),
+        [
+            ("Set name", SetBlackboard((key: (prop: Value("name")), value: (prop: Value(Str("Bob")))))),
+            ("Count name", IncrementBlackboard((key: (prop: Value("name"))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Count a name",
+        "[2] STARTED Set name",
+        "[2] SUCCESS Set name",
+        "[3] STARTED Count name",
+        "[3] FAILURE Count name",
+        "[1] FAILURE Count a name",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
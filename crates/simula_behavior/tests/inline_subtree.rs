@@ -0,0 +1,244 @@
+use bevy::{asset::AssetPath, prelude::*, utils::HashMap};
+use simula_behavior::{asset::inline_subtrees, prelude::*, test::*, BehaviorTrace};
+
+fn load_from(
+    documents: &HashMap<&str, &str>,
+) -> impl FnMut(&str) -> Result<BehaviorFile<TestBehavior>, BehaviorInlineError> + '_ {
+    move |asset| {
+        let document = documents
+            .get(asset)
+            .ok_or_else(|| BehaviorInlineError::Loading(asset.to_string()))?;
+        BehaviorFile::from_document(document).map_err(|err| BehaviorInlineError::Invalid {
+            asset: asset.to_string(),
+            message: err.to_string(),
+        })
+    }
+}
+
+fn names(behavior: &Behavior<TestBehavior>, names_list: &mut Vec<String>) {
+    names_list.push(behavior.name().to_string());
+    for child in behavior.nodes() {
+        names(child, names_list);
+    }
+}
+
+#[test]
+fn inline_nested_subtrees() {
+    let root = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Open door", Subtree((asset: "door.bht.ron", scope: Shared))),
+            ("Leave", Debug(())),
+        ],
+    )
+    "#;
+    let documents = HashMap::from_iter([
+        (
+            "door.bht.ron",
+            r#"
+            (
+                blackboard: [(key: "door", default: Str("front"))],
+                root: ("Door", Sequencer(()), [
+                    ("Unlock", Subtree((asset: "unlock.bht.ron", scope: Shared))),
+                    ("Push", Debug(())),
+                ]),
+            )
+            "#,
+        ),
+        ("unlock.bht.ron", r#"("Use key", Debug(()))"#),
+    ]);
+
+    let file = BehaviorFile::<TestBehavior>::from_document(root).unwrap();
+    let file = inline_subtrees(&file, None, load_from(&documents)).unwrap();
+    let mut node_names = vec![];
+    names(&file.root, &mut node_names);
+    assert_eq!(
+        node_names,
+        vec![
+            "Patrol",
+            "Open door",
+            "Door",
+            "Unlock",
+            "Use key",
+            "Push",
+            "Leave"
+        ]
+    );
+    assert!(file.blackboard.get("door").is_some());
+}
+
+#[test]
+fn inline_recursive_subtrees() {
+    let root = r#"("Patrol", Subtree((asset: "a.bht.ron", scope: Shared)))"#;
+    let documents = HashMap::from_iter([
+        (
+            "a.bht.ron",
+            r#"("A", Subtree((asset: "b.bht.ron", scope: Shared)))"#,
+        ),
+        (
+            "b.bht.ron",
+            r#"("B", Subtree((asset: "a.bht.ron", scope: Shared)))"#,
+        ),
+    ]);
+
+    let file = BehaviorFile::<TestBehavior>::from_document(root).unwrap();
+    let res = inline_subtrees(&file, Some("patrol.bht.ron"), load_from(&documents));
+    assert_eq!(
+        res.unwrap_err(),
+        BehaviorInlineError::Cycle(vec![
+            "a.bht.ron".to_string(),
+            "b.bht.ron".to_string(),
+            "a.bht.ron".to_string(),
+        ])
+    );
+}
+
+// App loading the tree from `root`, with the subtree documents in memory
+fn subtree_app(subtrees: BehaviorSubtrees, root: &str, documents: &[(&str, &str)]) -> App {
+    let mut app = App::new();
+    app.add_plugin(bevy::core::TaskPoolPlugin::default());
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .insert_resource(subtrees)
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default())
+        .edit_schedule(BehaviorSchedule, |schedule| {
+            schedule.add_system(subtree::run::<TestBehavior>);
+        });
+
+    // Loading a path finds the document already there, instead of a file
+    let mut behavior_documents = app.world.resource_mut::<Assets<BehaviorDocument>>();
+    for (asset, document) in documents {
+        behavior_documents.set_untracked(AssetPath::from(*asset), BehaviorDocument::new(*document));
+    }
+    let root = behavior_documents.add(BehaviorDocument::new(root));
+    app.world.spawn((
+        root,
+        BehaviorTree::<TestBehavior>::default(),
+        BehaviorTreeReset::<TestBehavior>::default(),
+    ));
+    app
+}
+
+// Trace without node indices
+fn trace_names(app: &App) -> Vec<String> {
+    app.world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1.to_string())
+        .collect()
+}
+
+#[test]
+fn inline_subtrees_app() {
+    let root = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Open door", Subtree((asset: "door.bht.ron", scope: Shared))),
+            ("Leave", Debug(())),
+        ],
+    )
+    "#;
+    let door = r#"
+    ("Door", Sequencer(()), [
+        ("Unlock", Subtree((asset: "unlock.bht.ron", scope: Shared))),
+        ("Push", Debug(())),
+    ])
+    "#;
+    let documents = [
+        ("door.bht.ron", door),
+        ("unlock.bht.ron", r#"("Use key", Debug(()))"#),
+    ];
+    let mut app = subtree_app(BehaviorSubtrees::Inline, root, &documents);
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+
+    // Subtrees run in the tree, none of them is loaded as a tree of its own
+    let trace = trace_names(&app);
+    println!("{:#?}", trace);
+    let successes: Vec<&String> = trace
+        .iter()
+        .filter(|trace| trace.starts_with("SUCCESS"))
+        .collect();
+    assert_eq!(
+        successes,
+        vec![
+            "SUCCESS Use key",
+            "SUCCESS Unlock",
+            "SUCCESS Push",
+            "SUCCESS Door",
+            "SUCCESS Open door",
+            "SUCCESS Leave",
+            "SUCCESS Patrol",
+        ]
+    );
+    let mut subtrees = app
+        .world
+        .query_filtered::<(), (With<BehaviorNode>, With<BehaviorTree<TestBehavior>>)>();
+    assert_eq!(subtrees.iter(&app.world).count(), 0);
+
+    // Changing a subtree document reloads the tree, the tree runs again
+    app.world
+        .resource_mut::<Assets<BehaviorDocument>>()
+        .set_untracked(
+            AssetPath::from("unlock.bht.ron"),
+            BehaviorDocument::new(r#"("Pick lock", Debug(()))"#),
+        );
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    let trace = trace_names(&app);
+    println!("{:#?}", trace);
+    assert!(trace.contains(&"SUCCESS Pick lock".to_string()));
+    assert_eq!(trace.last().unwrap(), "SUCCESS Patrol");
+}
+
+#[test]
+fn runtime_recursive_subtree() {
+    let root = r#"("Loop", Subtree((asset: "loop.bht.ron")))"#;
+    let documents = [(
+        "loop.bht.ron",
+        r#"("Again", Subtree((asset: "loop.bht.ron")))"#,
+    )];
+    let mut app = subtree_app(BehaviorSubtrees::Runtime, root, &documents);
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+
+    // The subtree is loaded once, then found in its own ancestors
+    let trace = trace_names(&app);
+    println!("{:#?}", trace);
+    assert!(trace.contains(&"FAILURE Again".to_string()));
+    assert_eq!(trace.last().unwrap(), "FAILURE Loop");
+}
+
+#[test]
+fn inline_skips_isolated_subtrees() {
+    let root = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Open door", Subtree((asset: "door.bht.ron", scope: Shared))),
+            ("Guard", Subtree((asset: "guard.bht.ron"))),
+        ],
+    )
+    "#;
+    let documents = HashMap::from_iter([
+        ("door.bht.ron", r#"("Push", Debug(()))"#),
+        ("guard.bht.ron", r#"("Look around", Debug(()))"#),
+    ]);
+
+    // Isolated subtrees keep their own blackboard, they load when they run
+    let file = BehaviorFile::<TestBehavior>::from_document(root).unwrap();
+    let file = inline_subtrees(&file, None, load_from(&documents)).unwrap();
+    let mut node_names = vec![];
+    names(&file.root, &mut node_names);
+    assert_eq!(node_names, vec!["Patrol", "Open door", "Push", "Guard"]);
+}
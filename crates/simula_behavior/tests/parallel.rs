@@ -0,0 +1,132 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn parallel_success_threshold() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: (prop: Value(2)))),
+        [
+            ("Do a thing", Debug(())),
+            ("Do another", Debug((fail: (prop: Value(true))))),
+            ("Do more", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Run until two good",
+        "[2] STARTED Do a thing",
+        "[3] STARTED Do another",
+        "[4] STARTED Do more",
+        "[2] SUCCESS Do a thing",
+        "[4] SUCCESS Do more",
+        "[3] FAILURE Do another",
+        "[1] SUCCESS Run until two good",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn parallel_failure_threshold() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: (prop: Value(2)))),
+        [
+            ("Do a thing", Debug((fail: (prop: Value(true))))),
+            ("Do another", Debug((fail: (prop: Value(true))))),
+            ("Do more", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Run until two good",
+        "[2] STARTED Do a thing",
+        "[3] STARTED Do another",
+        "[4] STARTED Do more",
+        "[2] FAILURE Do a thing",
+        "[3] FAILURE Do another",
+        "[4] SUCCESS Do more",
+        "[1] FAILURE Run until two good",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn parallel_policy_stop() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: (prop: Value(2)), policy: Stop)),
+        [
+            ("Do a thing", Debug(())),
+            ("Do another", Debug(())),
+            (
+                "Do a few more",
+                Sequencer(()),
+                [
+                    ("Do more", Debug(())),
+                    ("Do even more", Debug(())),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Run until two good",
+        "[2] STARTED Do a thing",
+        "[3] STARTED Do another",
+        "[4] STARTED Do a few more",
+        "[2] SUCCESS Do a thing",
+        "[3] SUCCESS Do another",
+        "[5] STARTED Do more",
+        "[5] SUCCESS Do more",
+        "[1] SUCCESS Run until two good",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn parallel_policy_wait() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: (prop: Value(2)), policy: Wait)),
+        [
+            ("Do a thing", Debug(())),
+            ("Do another", Debug(())),
+            (
+                "Do a few more",
+                Sequencer(()),
+                [
+                    ("Do more", Debug(())),
+                    ("Do even more", Debug(())),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Run until two good",
+        "[2] STARTED Do a thing",
+        "[3] STARTED Do another",
+        "[4] STARTED Do a few more",
+        "[2] SUCCESS Do a thing",
+        "[3] SUCCESS Do another",
+        "[5] STARTED Do more",
+        "[5] SUCCESS Do more",
+        "[6] STARTED Do even more",
+        "[6] SUCCESS Do even more",
+        "[4] SUCCESS Do a few more",
+        "[1] SUCCESS Run until two good",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
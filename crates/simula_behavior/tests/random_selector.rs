@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn trace_with_seed(behavior: &str, seed: u64) -> BehaviorTrace {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .insert_resource(BehaviorSeed(seed));
+    spawn_test_tree(&mut app, &behavior, default());
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+#[test]
+fn random_selector_weights() {
+    let behavior = r#"
+    (
+        "Select random action",
+        RandomSelector((weights: [0.0, 0.0, 1.0])),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Select random action",
+        "[4] STARTED Do action 2",
+        "[4] SUCCESS Do action 2",
+        "[1] SUCCESS Select random action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn random_selector_seeded() {
+    let behavior = r#"
+    (
+        "Select random action",
+        RandomSelector(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+            ("Do action 3", Debug(())),
+        ],
+    )
+    "#;
+
+    // Same seed, same choice
+    let mut picked = vec![];
+    for seed in 0..16 {
+        let trace = trace_with_seed(behavior, seed);
+        assert_eq!(trace, trace_with_seed(behavior, seed));
+        assert_eq!(trace.0.len(), 4);
+        picked.push(trace.0[1].clone());
+    }
+
+    // Other seeds, other choices
+    picked.sort();
+    picked.dedup();
+    assert!(picked.len() > 1);
+}
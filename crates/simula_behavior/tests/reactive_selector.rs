@@ -0,0 +1,68 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn reactive_selector_preempts_running_child() {
+    let behavior = r#"
+    (
+        "Attack or patrol",
+        ReactiveSelector(()),
+        [
+            (
+                "Enemy visible?",
+                Guard((
+                    condition: (
+                        prop: Eval(
+                            eval: "blackboard.state = blackboard.state + 1; blackboard.state > 3",
+                        ),
+                    ),
+                )),
+                [
+                    ("Attack", Debug(())),
+                ]
+            ),
+            ("Patrol", Wait((duration: (prop: Value(1000.0))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Attack or patrol",
+        "[2] STARTED Enemy visible?",
+        "[2] FAILURE Enemy visible?",
+        "[4] STARTED Patrol",
+        "[4] ABORTED Patrol",
+        "[2] STARTED Enemy visible?",
+        "[3] STARTED Attack",
+        "[3] SUCCESS Attack",
+        "[2] SUCCESS Enemy visible?",
+        "[1] SUCCESS Attack or patrol",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn reactive_selector_simple() {
+    let behavior = r#"
+    (
+        "Select first successful action",
+        ReactiveSelector(()),
+        [
+            ("Do action 0", Debug((fail: (prop: Value(true))))),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug((fail: (prop: Value(true))))),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Select first successful action",
+        "[2] STARTED Do action 0",
+        "[2] FAILURE Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] SUCCESS Do action 1",
+        "[1] SUCCESS Select first successful action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,105 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn reactive_sequencer_aborts_running_child() {
+    let behavior = r#"
+    (
+        "Patrol while allowed",
+        ReactiveSequencer(()),
+        [
+            (
+                "Can patrol?",
+                Guard((
+                    condition: (
+                        prop: Eval(
+                            eval: "blackboard.state = blackboard.state + 1; blackboard.state < 4",
+                        ),
+                    ),
+                )),
+                [
+                    ("Check", Debug(())),
+                ]
+            ),
+            ("Patrol", Wait((duration: (prop: Value(1000.0))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Patrol while allowed",
+        "[2] STARTED Can patrol?",
+        "[3] STARTED Check",
+        "[3] SUCCESS Check",
+        "[2] SUCCESS Can patrol?",
+        "[4] STARTED Patrol",
+        "[4] ABORTED Patrol",
+        "[1] FAILURE Patrol while allowed",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn reactive_sequencer_simple() {
+    let behavior = r#"
+    (
+        "Do in order",
+        ReactiveSequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug((fail: (prop: Value(true))))),
+            ("Do action 2", Debug(())),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Do in order",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] FAILURE Do action 1",
+        "[1] FAILURE Do in order",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn reactive_sequencer_guard_error_fails() {
+    let behavior = r#"
+    (
+        "Patrol while allowed",
+        ReactiveSequencer(()),
+        [
+            (
+                "Can patrol?",
+                Guard((
+                    condition: (
+                        prop: Eval(
+                            eval: "blackboard.state = blackboard.state + 1; if blackboard.state < 4 { true } else { missing }",
+                        ),
+                    ),
+                )),
+                [
+                    ("Check", Debug(())),
+                ]
+            ),
+            ("Patrol", Wait((duration: (prop: Value(1000.0))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Patrol while allowed",
+        "[2] STARTED Can patrol?",
+        "[3] STARTED Check",
+        "[3] SUCCESS Check",
+        "[2] SUCCESS Can patrol?",
+        "[4] STARTED Patrol",
+        "[4] ABORTED Patrol",
+        "[1] FAILURE Patrol while allowed",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    prelude::*,
+    protocol::BehaviorState,
+    recorder::{record, BehaviorRecording},
+    test::*,
+};
+
+fn record_behavior(
+    behavior: &str,
+    capacity: usize,
+    frames: usize,
+) -> BehaviorRecording<TestBehavior> {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .add_asset::<BehaviorAsset<TestBehavior>>()
+        .add_system(record::<TestBehavior>.in_schedule(BehaviorPostTickSchedule));
+
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .add(BehaviorAsset {
+            behavior: behavior.clone(),
+            blackboard: default(),
+            file_name: None,
+        });
+    let entity = spawn_test_tree(&mut app, &behavior, default());
+    app.world.entity_mut(entity).insert((
+        behavior_handle,
+        BehaviorRecorder::<TestBehavior>::new(capacity),
+    ));
+
+    for _ in 0..frames {
+        app.update();
+    }
+
+    app.world
+        .get::<BehaviorRecorder<TestBehavior>>(entity)
+        .unwrap()
+        .recording
+        .clone()
+}
+
+#[test]
+fn recorder_keeps_last_ticks() {
+    let behavior = r#"
+    (
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+        ],
+    )
+    "#;
+    let recording = record_behavior(behavior, 3, 10);
+
+    assert!(recording.behavior.is_some());
+    let ticks: Vec<u64> = recording.ticks.iter().map(|tick| tick.tick).collect();
+    assert_eq!(ticks, vec![8, 9, 10]);
+
+    // Tree completed, every node is recorded
+    let telemetry = &recording.ticks.back().unwrap().telemetry;
+    assert_eq!(telemetry.1, BehaviorState::Success);
+    assert!(telemetry.2.is_some());
+    assert_eq!(telemetry.3.len(), 2);
+}
+
+#[test]
+fn recorder_save_and_load() {
+    let behavior = r#"("Do action", Debug(()))"#;
+    let recording = record_behavior(behavior, 5, 4);
+
+    let path = std::env::temp_dir().join("simula_behavior_recorder_test.bhr.ron");
+    recording.save(&path).unwrap();
+    let loaded = BehaviorRecording::<TestBehavior>::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.ticks.len(), recording.ticks.len());
+    assert_eq!(
+        loaded.behavior.map(|behavior| behavior.name().to_string()),
+        Some("Do action".to_string())
+    );
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    prelude::*,
+    protocol::{
+        BehaviorFileId, BehaviorFileName, BehaviorProtocolClient, BehaviorProtocolServer,
+        BehaviorServer, BehaviorState, BehaviorTelemetry, RemoteEntity, StartOption,
+    },
+    remote::{connect, listen},
+    test::*,
+};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn remote_loopback() {
+    let (server, addr) = listen::<TestBehavior>("127.0.0.1:0").unwrap();
+    let client = connect::<TestBehavior>(addr.to_string());
+
+    // Server is told when a client connects
+    let message = server.receiver.recv_timeout(TIMEOUT).unwrap();
+    assert!(matches!(message, BehaviorProtocolClient::Connected));
+
+    // Client requests are received by the server
+    let file_id = BehaviorFileId::new();
+    let entity = RemoteEntity::new(Entity::from_raw(42), "Agent");
+    client
+        .sender
+        .send(BehaviorProtocolClient::Start(
+            file_id.clone(),
+            BehaviorFileName("bht/u/agent".into()),
+            StartOption::Attach(entity.clone()),
+            None,
+        ))
+        .unwrap();
+    let message = server.receiver.recv_timeout(TIMEOUT).unwrap();
+    let BehaviorProtocolClient::Start(id, name, StartOption::Attach(attached), None) = message
+    else {
+        panic!("Unexpected message: {:?}", message);
+    };
+    assert_eq!(id, file_id);
+    assert_eq!(*name, "bht/u/agent");
+    assert_eq!(attached.to_entity(), Entity::from_raw(42));
+
+    // Server replies are received by the client
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(r#"("Do a thing", Debug(()))"#);
+    server
+        .sender
+        .send(BehaviorProtocolServer::FileLoaded(
+            file_id.clone(),
+            behavior.unwrap(),
+        ))
+        .unwrap();
+    server
+        .sender
+        .send(BehaviorProtocolServer::Telemetry(
+            file_id.clone(),
+            BehaviorTelemetry(
+                Some(entity.clone()),
+                BehaviorState::Running,
+                Some(TestBehavior::default()),
+                vec![],
+            ),
+        ))
+        .unwrap();
+
+    let Ok(BehaviorProtocolServer::FileLoaded(id, behavior)) =
+        client.receiver.recv_timeout(TIMEOUT)
+    else {
+        panic!("Expected FileLoaded");
+    };
+    assert_eq!(id, file_id);
+    assert_eq!(behavior.name(), "Do a thing");
+
+    let Ok(BehaviorProtocolServer::Telemetry(id, telemetry)) =
+        client.receiver.recv_timeout(TIMEOUT)
+    else {
+        panic!("Expected Telemetry");
+    };
+    assert_eq!(id, file_id);
+    assert_eq!(telemetry.0, Some(entity));
+    assert!(matches!(telemetry.1, BehaviorState::Running));
+}
+
+#[test]
+fn remote_server_address_in_use() {
+    let (_server, addr) = listen::<TestBehavior>("127.0.0.1:0").unwrap();
+
+    // Server is disabled, instead of failing the app
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.add_plugin(BehaviorRemoteServerPlugin::<TestBehavior>::new(
+        addr.to_string(),
+    ))
+    .add_plugin(BehaviorServerPlugin::<TestBehavior>::default());
+    assert!(!app
+        .world
+        .contains_resource::<BehaviorServer<TestBehavior>>());
+    app.update();
+}
+
+#[test]
+fn remote_client_drops_messages_while_disconnected() {
+    // Find a free address, nothing listens on it yet
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = connect::<TestBehavior>(addr.to_string());
+    let stale_id = BehaviorFileId::new();
+    client
+        .sender
+        .send(BehaviorProtocolClient::LoadFile(stale_id))
+        .unwrap();
+
+    // Let the client fail to connect at least once
+    std::thread::sleep(Duration::from_millis(1500));
+    let (server, _) = listen::<TestBehavior>(&addr.to_string()).unwrap();
+    let message = server.receiver.recv_timeout(TIMEOUT).unwrap();
+    assert!(matches!(message, BehaviorProtocolClient::Connected));
+
+    // Only messages sent while connected are received
+    let file_id = BehaviorFileId::new();
+    client
+        .sender
+        .send(BehaviorProtocolClient::LoadFile(file_id.clone()))
+        .unwrap();
+    let message = server.receiver.recv_timeout(TIMEOUT).unwrap();
+    let BehaviorProtocolClient::LoadFile(id) = message else {
+        panic!("Unexpected message: {:?}", message);
+    };
+    assert_eq!(id, file_id);
+}
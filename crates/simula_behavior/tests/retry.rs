@@ -0,0 +1,133 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn retry_until_attempts() {
+    let behavior = r#"
+    (
+        "Try a few times",
+        Retry((attempts: (prop: Value(3)))),
+        [
+            ("Do failing action", Debug((fail:true))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] FAILURE Try a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_until_success() {
+    let behavior = r#"
+    (
+        "Try a few times",
+        Retry((attempts: (prop: Value(5)))),
+        [
+            ("Count and check", Sequencer(()), [
+                ("Count", IncrementBlackboard((key: (prop: Value("count"))))),
+                ("Check count", CompareBlackboard((key: (prop: Value("count")), op: GreaterOrEqual, value: (prop: Value(Int(2)))))),
+            ]),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try a few times",
+        "[2] STARTED Count and check",
+        "[3] STARTED Count",
+        "[3] SUCCESS Count",
+        "[4] STARTED Check count",
+        "[4] FAILURE Check count",
+        "[2] FAILURE Count and check",
+        "[1] STARTED Try a few times",
+        "[2] STARTED Count and check",
+        "[3] STARTED Count",
+        "[3] SUCCESS Count",
+        "[4] STARTED Check count",
+        "[4] SUCCESS Check count",
+        "[2] SUCCESS Count and check",
+        "[1] SUCCESS Try a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_backoff() {
+    let behavior = r#"
+    (
+        "Try again later",
+        Retry((attempts: (prop: Value(2)), backoff: (prop: Value(1.0)))),
+        [
+            ("Do failing action", Debug((fail:true))),
+        ]
+    )
+    "#;
+
+    // Waiting before the second attempt
+    let timing = BehaviorTiming::stepped(TEST_TIMESTEP);
+    let trace = trace_behavior_for_frames(behavior, timing.clone(), 30);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try again later",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try again later",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+
+    // Second attempt after the backoff
+    let trace = trace_behavior_for_frames(behavior, timing, 120);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try again later",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try again later",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] FAILURE Try again later",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_default_attempts() {
+    // Without attempts, it does not retry forever
+    let behavior = r#"
+    (
+        "Try a few times",
+        Retry(()),
+        [
+            ("Do failing action", Debug((fail:true))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] STARTED Try a few times",
+        "[2] STARTED Do failing action",
+        "[2] FAILURE Do failing action",
+        "[1] FAILURE Try a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
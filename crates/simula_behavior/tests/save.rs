@@ -0,0 +1,240 @@
+use bevy::{
+    prelude::*,
+    utils::{Duration, Instant},
+};
+use simula_behavior::{blackboard, prelude::*, test::*, BehaviorTrace};
+use simula_script::ScriptContext;
+
+const PATROL: &str = r#"
+(
+    "Patrol",
+    Sequencer(()),
+    [
+        ("Count patrol", SetBlackboard((key: (prop: Value("patrols")), value: (prop: Value(Int(1)))))),
+        ("Wait at A", Wait((duration: (prop: Value(1.0))))),
+        ("Go to B", Debug(())),
+    ],
+)
+"#;
+
+const GUARD: &str = r#"
+(
+    "Guard",
+    Sequencer(()),
+    [
+        ("Wait at gate", Wait((duration: (prop: Value(0.1))))),
+    ],
+)
+"#;
+
+// Spawn a behavior tree from its asset, restored from a save if any
+fn spawn_tree(behavior: &str, save: Option<BehaviorTreeSave>) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default());
+    let tree = spawn_tree_in(&mut app, behavior, save);
+    (app, tree)
+}
+
+fn spawn_tree_in(app: &mut App, behavior: &str, save: Option<BehaviorTreeSave>) -> Entity {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .add(BehaviorAsset {
+            behavior,
+            blackboard: default(),
+            file_name: None,
+        });
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(BehaviorTree::<TestBehavior>::create_script_context());
+    let mut tree = app.world.spawn((
+        behavior_handle,
+        script_ctx_handle,
+        BehaviorTree::<TestBehavior>::default(),
+        BehaviorTreeReset::<TestBehavior>::default(),
+    ));
+    if let Some(save) = save {
+        tree.insert(save);
+    }
+    tree.id()
+}
+
+// Run a few frames and return the trace without node indices, as nodes are
+// spawned again when restored
+fn run_frames(app: &mut App, frames: usize) -> Vec<String> {
+    for _ in 0..frames {
+        app.update();
+    }
+    app.world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1.to_string())
+        .collect()
+}
+
+// Save a running tree, through RON as in a save file
+fn save_tree(app: &App, tree: Entity) -> BehaviorTreeSave {
+    let save = BehaviorTreeSave::from_world::<TestBehavior>(&app.world, tree).unwrap();
+    let save = ron::to_string(&save).unwrap();
+    ron::from_str(&save).unwrap()
+}
+
+#[test]
+fn save_and_restore() {
+    let (mut app, tree) = spawn_tree(PATROL, None);
+    let trace = run_frames(&mut app, 40);
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Patrol",
+            "STARTED Count patrol",
+            "SUCCESS Count patrol",
+            "STARTED Wait at A",
+        ]
+    );
+    let save = save_tree(&app, tree);
+
+    // The wait resumes where it was, the blackboard is kept
+    let (mut app, tree) = spawn_tree(PATROL, Some(save));
+    let trace = run_frames(&mut app, 40);
+    assert_eq!(
+        trace,
+        vec![
+            "SUCCESS Wait at A",
+            "STARTED Go to B",
+            "SUCCESS Go to B",
+            "SUCCESS Patrol",
+        ]
+    );
+    let script_ctx = app.world.get::<Handle<ScriptContext>>(tree).unwrap();
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(script_ctx);
+    assert_eq!(
+        blackboard::get_value(script_ctx.unwrap(), "patrols"),
+        Some(BlackboardValue::Int(1))
+    );
+}
+
+#[test]
+fn restore_with_frame_clock() {
+    let (mut app, tree) = spawn_tree(PATROL, None);
+    run_frames(&mut app, 40);
+    let save = save_tree(&app, tree);
+
+    // Restore in an app running for a while, its clock follows `Time`
+    let mut app = App::new();
+    app.insert_resource(Time::default());
+    test_app(&mut app);
+    app.add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default());
+    let mut now = Instant::now();
+    app.world.resource_mut::<Time>().update_with_instant(now);
+    now += Duration::from_secs(10);
+    app.world.resource_mut::<Time>().update_with_instant(now);
+    app.update();
+    spawn_tree_in(&mut app, PATROL, Some(save));
+    let mut step_frames = |app: &mut App, frames: usize| {
+        for _ in 0..frames {
+            now += Duration::from_secs_f64(TEST_TIMESTEP);
+            app.world.resource_mut::<Time>().update_with_instant(now);
+            app.update();
+        }
+        run_frames(app, 0)
+    };
+
+    // The wait resumes with the time it had left, not the time of the clock
+    assert!(step_frames(&mut app, 10).is_empty());
+    let trace = step_frames(&mut app, 40);
+    assert_eq!(
+        trace,
+        vec![
+            "SUCCESS Wait at A",
+            "STARTED Go to B",
+            "SUCCESS Go to B",
+            "SUCCESS Patrol",
+        ]
+    );
+}
+
+#[test]
+fn restore_mismatch_restarts() {
+    let (mut app, tree) = spawn_tree(PATROL, None);
+    run_frames(&mut app, 40);
+    let save = save_tree(&app, tree);
+
+    // Saved nodes do not match the tree, so it starts again
+    let (mut app, _) = spawn_tree(GUARD, Some(save));
+    let trace = run_frames(&mut app, 40);
+    assert_eq!(
+        trace,
+        vec![
+            "STARTED Guard",
+            "STARTED Wait at gate",
+            "SUCCESS Wait at gate",
+            "SUCCESS Guard",
+        ]
+    );
+}
+
+const SHUFFLED: &str = r#"
+(
+    "Patrol",
+    Repeater((repeat: Times(6))),
+    [
+        ("Round", Sequencer(()), [
+            ("Visit", ShuffledSequencer(()), [
+                ("Go to A", Debug(())),
+                ("Go to B", Debug(())),
+                ("Go to C", Debug(())),
+            ]),
+            ("Rest", Wait((duration: (prop: Value(1.0))))),
+        ]),
+    ],
+)
+"#;
+
+#[test]
+fn restore_random_generator() {
+    let (mut app, _) = spawn_tree(SHUFFLED, None);
+    let expected_trace = run_frames(&mut app, 600);
+    assert_eq!(expected_trace.last().unwrap(), "SUCCESS Patrol");
+
+    // Save while resting after the first round
+    let (mut app, tree) = spawn_tree(SHUFFLED, None);
+    let mut trace = run_frames(&mut app, 40);
+    assert_eq!(trace.last().unwrap(), "STARTED Rest");
+    let save = save_tree(&app, tree);
+
+    // The next rounds visit in the same order as without the save
+    let (mut app, _) = spawn_tree(SHUFFLED, Some(save));
+    trace.extend(run_frames(&mut app, 560));
+    assert_eq!(trace, expected_trace);
+}
+
+#[test]
+fn restore_shared_cooldowns() {
+    // Key used a quarter second before the save
+    let (mut app, _) = spawn_tree(GUARD, None);
+    run_frames(&mut app, 30);
+    let clock = app.world.resource::<BehaviorClock>().clone();
+    let mut cooldowns = BehaviorCooldowns::default();
+    cooldowns.insert("chatter".into(), clock.elapsed_seconds_f64() - 0.25);
+    let save = ron::to_string(&cooldowns.save_time(&clock)).unwrap();
+
+    // Still used a quarter second before, by the clock of the other app
+    let (mut app, _) = spawn_tree(GUARD, None);
+    run_frames(&mut app, 90);
+    let clock = app.world.resource::<BehaviorClock>().clone();
+    let cooldowns = ron::from_str::<BehaviorCooldowns>(&save).unwrap();
+    let cooldowns = cooldowns.restore_time(&clock);
+    let time = clock.elapsed_seconds_f64() - cooldowns["chatter"];
+    assert!((time - 0.25).abs() < 1e-9);
+}
@@ -0,0 +1,36 @@
+use simula_behavior::prelude::*;
+
+#[test]
+fn server_config_discovers_nested_files() {
+    let asset_folder = std::env::temp_dir().join("simula_behavior_server_config_test");
+    std::fs::remove_dir_all(&asset_folder).ok();
+    for file in [
+        "bht/combat/attack.bht.ron",
+        "bht/combat/melee/slash.bht.ron",
+        "bht/dialog/greet.bht.ron",
+        "bht/dialog/notes.txt",
+        "bht/other/ignored.bht.ron",
+    ] {
+        let path = asset_folder.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+
+    let config = BehaviorServerConfig::new(["bht/dialog", "bht/combat", "bht/missing"])
+        .with_asset_folder(asset_folder.to_string_lossy().to_string());
+    let file_names: Vec<String> = config
+        .discover()
+        .iter()
+        .map(|file_name| file_name.to_string())
+        .collect();
+    std::fs::remove_dir_all(&asset_folder).ok();
+
+    assert_eq!(
+        file_names,
+        vec![
+            "bht/combat/attack",
+            "bht/combat/melee/slash",
+            "bht/dialog/greet",
+        ]
+    );
+}
@@ -0,0 +1,51 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn set_blackboard_value() {
+    let behavior = r#"
+    (
+        "Set and check",
+        Sequencer(()),
+        [
+            ("Set count", SetBlackboard((key: (prop: Value("count")), value: (prop: Value(Int(3)))))),
+            ("Check count", CompareBlackboard((key: (prop: Value("count")), value: (prop: Value(Int(3)))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Set and check",
+        "[2] STARTED Set count",
+        "[2] SUCCESS Set count",
+        "[3] STARTED Check count",
+        "[3] SUCCESS Check count",
+        "[1] SUCCESS Set and check",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn set_blackboard_eval() {
+    let behavior = r#"
+    (
+        "Set and check",
+        Sequencer(()),
+        [
+            ("Set count", SetBlackboard((key: (prop: Value("count")), value: (prop: Eval(eval: "Int(blackboard.state + 5)"))))),
+            ("Check count", CompareBlackboard((key: (prop: Value("count")), value: (prop: Value(Int(5)))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Set and check",
+        "[2] STARTED Set count",
+        "[2] SUCCESS Set count",
+        "[3] STARTED Check count",
+        "[3] SUCCESS Check count",
+        "[1] SUCCESS Set and check",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
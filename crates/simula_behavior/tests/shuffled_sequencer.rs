@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn trace_with_seed(behavior: &str, seed: u64) -> BehaviorTrace {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .insert_resource(BehaviorSeed(seed));
+    spawn_test_tree(&mut app, &behavior, default());
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+// Names of the children started, in order
+fn started_children(trace: &BehaviorTrace) -> Vec<String> {
+    trace
+        .0
+        .iter()
+        .filter(|trace| trace.contains("STARTED Do action"))
+        .map(|trace| trace.split_once("STARTED ").unwrap().1.to_string())
+        .collect()
+}
+
+#[test]
+fn shuffled_sequencer_runs_all_children() {
+    let behavior = r#"
+    (
+        "Sequence of random actions",
+        ShuffledSequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+            ("Do action 3", Debug(())),
+        ],
+    )
+    "#;
+
+    let mut orders = vec![];
+    for seed in 0..16 {
+        let trace = trace_with_seed(behavior, seed);
+        assert_eq!(trace, trace_with_seed(behavior, seed));
+        assert_eq!(
+            trace.0.last().unwrap(),
+            "[1] SUCCESS Sequence of random actions"
+        );
+
+        let order = started_children(&trace);
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec!["Do action 0", "Do action 1", "Do action 2", "Do action 3"]
+        );
+        orders.push(order);
+    }
+
+    // Other seeds, other orders
+    orders.sort();
+    orders.dedup();
+    assert!(orders.len() > 1);
+}
+
+#[test]
+fn shuffled_sequencer_weights() {
+    let behavior = r#"
+    (
+        "Sequence of random actions",
+        ShuffledSequencer((weights: [0.0, 0.0, 1.0])),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+        ],
+    )
+    "#;
+
+    // Children with no weight come last
+    for seed in 0..8 {
+        let trace = trace_with_seed(behavior, seed);
+        assert_eq!(started_children(&trace)[0], "Do action 2");
+    }
+}
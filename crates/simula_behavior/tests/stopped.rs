@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+
+#[derive(Default, Resource)]
+struct Halted(Vec<String>);
+
+// Release resources held by nodes that were interrupted
+fn on_halt(mut halted: ResMut<Halted>, stopped: Query<&Name, With<BehaviorStopped>>) {
+    for name in &stopped {
+        halted.0.push(name.to_string());
+    }
+}
+
+fn halted_nodes(behavior: &str) -> Vec<String> {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .init_resource::<Halted>()
+        .edit_schedule(BehaviorSchedule, |schedule| {
+            schedule.add_system(on_halt);
+        });
+    spawn_test_tree(&mut app, &behavior, default());
+
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    app.world.resource_mut::<Halted>().0.clone()
+}
+
+#[test]
+fn timeout_stops_child() {
+    let behavior = r#"
+    (
+        "Wait with a time limit",
+        Timeout((duration: (prop: Value(0.5)))),
+        [
+            ("Wait too long", Wait((duration: (prop: Value(1000.0))))),
+        ]
+    )
+    "#;
+    assert_eq!(halted_nodes(behavior), vec!["Wait too long"]);
+}
+
+#[test]
+fn any_stops_running_children() {
+    let behavior = r#"
+    (
+        "Run until any good",
+        Any(()),
+        [
+            ("Do quickly", Debug(())),
+            ("Wait too long", Wait((duration: (prop: Value(1000.0))))),
+        ]
+    )
+    "#;
+    assert_eq!(halted_nodes(behavior), vec!["Wait too long"]);
+}
@@ -0,0 +1,152 @@
+use bevy::{asset::AssetPath, prelude::*};
+use simula_behavior::{blackboard, prelude::*, test::*, BehaviorTrace};
+use simula_script::ScriptContext;
+
+// Subtree document, doubles `x` into `y` and writes `done`
+const SUBTREE: &str = r#"
+(
+    blackboard: [(key: "x", default: Int(0))],
+    root: ("Double", Sequencer(()), [
+        ("Check x", CompareBlackboard((key: (prop: Value("x")), op: Equal, value: (prop: Value(Int(2)))))),
+        ("Set y", SetBlackboard((key: (prop: Value("y")), value: (prop: Value(Int(4)))))),
+        ("Set done", SetBlackboard((key: (prop: Value("done")), value: (prop: Value(Bool(true)))))),
+    ]),
+)
+"#;
+
+// Run a tree calling `sub.bht.ron`, loaded from memory, and return its trace
+// without node indices and the tree entity
+fn run_subtree(document: &str) -> (App, Vec<String>, Entity) {
+    let mut app = App::new();
+    app.add_plugin(bevy::core::TaskPoolPlugin::default());
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .add_asset::<BehaviorDocument>()
+        .add_plugin(BehaviorTreePlugin::<TestBehavior>::default())
+        .edit_schedule(BehaviorSchedule, |schedule| {
+            schedule.add_system(subtree::run::<TestBehavior>);
+        });
+
+    // Loading the path finds the document already there, instead of a file
+    app.world
+        .resource_mut::<Assets<BehaviorDocument>>()
+        .set_untracked(
+            AssetPath::from("sub.bht.ron"),
+            BehaviorDocument::new(SUBTREE),
+        );
+
+    let file = BehaviorFile::<TestBehavior>::from_document(document).unwrap();
+    let tree = spawn_test_tree(&mut app, &file.root, file.blackboard);
+
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+
+    let trace = app
+        .world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .map(|trace| trace.split_once("] ").unwrap().1.to_string())
+        .collect();
+    (app, trace, tree)
+}
+
+fn value(app: &App, tree: Entity, key: &str) -> Option<BlackboardValue> {
+    let script_ctx_handle = app.world.get::<Handle<ScriptContext>>(tree).unwrap();
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(script_ctx_handle)
+        .unwrap();
+    blackboard::get_value(script_ctx, key)
+}
+
+#[test]
+fn subtree_inputs_and_outputs() {
+    let document = r#"
+    (
+        blackboard: [(key: "a", default: Int(2)), (key: "b", default: Int(0))],
+        root: ("Call", Subtree((
+            asset: "sub.bht.ron",
+            inputs: [(parent: "a", child: "x")],
+            outputs: [(parent: "b", child: "y")],
+        ))),
+    )
+    "#;
+    let (app, trace, tree) = run_subtree(document);
+    println!("{:#?}", trace);
+    // The subtree only succeeds if `a` was copied into `x`
+    assert!(trace.contains(&"SUCCESS Check x".to_string()));
+    assert!(trace.contains(&"SUCCESS Call".to_string()));
+    // `y` was copied back into `b`, other subtree keys stay in the subtree
+    assert_eq!(value(&app, tree, "b"), Some(BlackboardValue::Int(4)));
+    assert_eq!(value(&app, tree, "done"), None);
+}
+
+#[test]
+fn subtree_missing_input() {
+    let document = r#"
+    (
+        "Call",
+        Subtree((asset: "sub.bht.ron", inputs: [(parent: "missing", child: "x")])),
+    )
+    "#;
+    let (_, trace, _) = run_subtree(document);
+    println!("{:#?}", trace);
+    assert!(trace.contains(&"FAILURE Call".to_string()));
+    assert!(!trace.contains(&"SUCCESS Call".to_string()));
+}
+
+#[test]
+fn subtree_missing_output() {
+    let document = r#"
+    (
+        blackboard: [(key: "a", default: Int(2))],
+        root: ("Call", Subtree((
+            asset: "sub.bht.ron",
+            inputs: [(parent: "a", child: "x")],
+            outputs: [(parent: "b", child: "missing")],
+        ))),
+    )
+    "#;
+    let (app, trace, tree) = run_subtree(document);
+    println!("{:#?}", trace);
+    // The subtree ran to the end, but its output could not be copied
+    assert!(trace.contains(&"SUCCESS Double".to_string()));
+    assert!(trace.contains(&"FAILURE Call".to_string()));
+    assert_eq!(value(&app, tree, "b"), None);
+}
+
+#[test]
+fn subtree_shared_scope() {
+    let document = r#"
+    (
+        blackboard: [(key: "x", default: Int(2))],
+        root: ("Call", Subtree((asset: "sub.bht.ron", scope: Shared))),
+    )
+    "#;
+    let (app, trace, tree) = run_subtree(document);
+    println!("{:#?}", trace);
+    // The subtree reads and writes the parent blackboard, without params
+    assert!(trace.contains(&"SUCCESS Call".to_string()));
+    assert_eq!(value(&app, tree, "y"), Some(BlackboardValue::Int(4)));
+    assert_eq!(value(&app, tree, "done"), Some(BlackboardValue::Bool(true)));
+}
+
+#[test]
+fn subtree_isolated_scope() {
+    let document = r#"
+    (
+        blackboard: [(key: "x", default: Int(2))],
+        root: ("Call", Subtree((asset: "sub.bht.ron"))),
+    )
+    "#;
+    let (app, trace, tree) = run_subtree(document);
+    println!("{:#?}", trace);
+    // The subtree has its own `x`, with the default of its document
+    assert!(trace.contains(&"FAILURE Check x".to_string()));
+    assert!(trace.contains(&"FAILURE Call".to_string()));
+    assert_eq!(value(&app, tree, "y"), None);
+    assert_eq!(value(&app, tree, "done"), None);
+}
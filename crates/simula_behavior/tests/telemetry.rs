@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, protocol::BehaviorState, test::*};
+use std::time::Duration;
+
+const BEHAVIOR: &str = r#"
+(
+    "Wait then act",
+    Sequencer(()),
+    [
+        ("Wait a long time", Wait((duration: (prop: Value(1000.0))))),
+        ("Do a thing", Debug(())),
+    ],
+)
+"#;
+
+fn spawn_tree() -> (App, Entity, Behavior<TestBehavior>) {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(BEHAVIOR).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    let tree = spawn_test_tree(&mut app, &behavior, default());
+    let root = app.world.get::<Children>(tree).unwrap()[0];
+    (app, root, behavior)
+}
+
+fn child(app: &App, root: Entity, index: usize) -> Entity {
+    app.world.get::<BehaviorChildren>(root).unwrap()[index]
+}
+
+#[test]
+fn telemetry_unchanged_omitted() {
+    let (mut app, root, behavior) = spawn_tree();
+    let mut tracker = TelemetryTracker::<TestBehavior>::default();
+
+    // First telemetry is complete
+    let telemetry = tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap()
+        .unwrap();
+    assert_eq!(telemetry.0.unwrap().to_entity(), root);
+    assert!(telemetry.2.is_some());
+    assert_eq!(telemetry.3.len(), 2);
+    assert!(telemetry.3.iter().all(|child| child.0.is_some()));
+
+    // Nothing changed, nothing is sent
+    let telemetry = tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap();
+    assert!(telemetry.is_none());
+
+    // Only the changed node and its parents are sent
+    let node = child(&app, root, 1);
+    app.world.entity_mut(node).insert(BehaviorFailure);
+    let telemetry = tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap()
+        .unwrap();
+    assert_eq!(telemetry.0.unwrap().to_entity(), root);
+    assert!(telemetry.3[0].0.is_none());
+    assert_eq!(telemetry.3[1].0.as_ref().unwrap().to_entity(), node);
+}
+
+#[test]
+fn telemetry_state_only_change_has_no_data() {
+    let (mut app, root, behavior) = spawn_tree();
+    let mut tracker = TelemetryTracker::<TestBehavior>::default();
+    tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap();
+
+    let node = child(&app, root, 1);
+    app.world.entity_mut(node).insert(BehaviorFailure);
+    let telemetry = tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap()
+        .unwrap();
+    assert_eq!(telemetry.3[1].1, BehaviorState::Failure);
+    assert!(telemetry.3[1].2.is_none());
+    // Parents are sent for their changed children, with no data either
+    assert!(telemetry.2.is_none());
+}
+
+#[test]
+fn telemetry_rate_waits_until_due() {
+    let (app, root, behavior) = spawn_tree();
+    let mut tracker = TelemetryTracker::<TestBehavior> {
+        rate: 10.0,
+        ..default()
+    };
+
+    // Nothing sent yet, telemetry is due
+    assert!(tracker.is_due(Duration::ZERO));
+    tracker
+        .build(&app.world, root, &behavior, Duration::ZERO)
+        .unwrap();
+
+    // Due again after 1 / rate seconds
+    assert!(!tracker.is_due(Duration::from_millis(50)));
+    assert!(tracker.is_due(Duration::from_millis(100)));
+
+    // Every frame with no rate
+    tracker.rate = 0.0;
+    assert!(tracker.is_due(Duration::from_millis(50)));
+
+    // Forgetting what was sent makes it due
+    tracker.rate = 10.0;
+    tracker.reset();
+    assert!(tracker.is_due(Duration::ZERO));
+}
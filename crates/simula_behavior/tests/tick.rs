@@ -0,0 +1,289 @@
+use bevy::{
+    prelude::*,
+    utils::{Duration, Instant},
+};
+use simula_behavior::{
+    prelude::{
+        Behavior, BehaviorClock, BehaviorNode, BehaviorPostTickSchedule, BehaviorSignal,
+        BehaviorStarted, BehaviorTiming,
+    },
+    test::*,
+    BehaviorTrace,
+};
+
+// Ticks run in each frame, with frames of the given lengths in seconds
+fn ticks_per_frame(timing: BehaviorTiming, frames: &[f64]) -> Vec<u64> {
+    let mut app = App::new();
+    app.insert_resource(Time::default());
+    test_app(&mut app);
+    app.insert_resource(timing);
+
+    let mut now = Instant::now();
+    app.world.resource_mut::<Time>().update_with_instant(now);
+    frames
+        .iter()
+        .map(|frame| {
+            now += Duration::from_secs_f64(*frame);
+            app.world.resource_mut::<Time>().update_with_instant(now);
+            let ticks = app.world.resource::<BehaviorClock>().ticks();
+            app.update();
+            app.world.resource::<BehaviorClock>().ticks() - ticks
+        })
+        .collect()
+}
+
+#[test]
+fn tick_stepped_clock() {
+    let behavior = r#"
+    (
+        "Wait for any",
+        Any(()),
+        [
+            ("Short wait", Wait((duration: (prop: Value(0.5))))),
+            ("Long wait", Wait((duration: (prop: Value(2.0))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior_with_timing(behavior, BehaviorTiming::stepped(0.25));
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Wait for any",
+        "[2] STARTED Short wait",
+        "[3] STARTED Long wait",
+        "[2] SUCCESS Short wait",
+        "[1] SUCCESS Wait for any",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn tick_max_handoffs() {
+    let behavior = r#"
+    (
+        "Sequencer of a few actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+            ("Do action 2", Debug(())),
+        ],
+    )
+    "#;
+    let timing = BehaviorTiming::stepped(TEST_TIMESTEP).with_max_handoffs(100);
+    let trace = trace_behavior_with_timing(behavior, timing);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of a few actions",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Do action 1",
+        "[3] SUCCESS Do action 1",
+        "[4] STARTED Do action 2",
+        "[4] SUCCESS Do action 2",
+        "[1] SUCCESS Sequencer of a few actions",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn tick_single_frame() {
+    let behavior = r#"
+    (
+        "Sequencer of nested actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            (
+                "Nested sequencer",
+                Sequencer(()),
+                [
+                    ("Do action 1", Debug(())),
+                    ("Do action 2", Debug(())),
+                ],
+            ),
+            ("Do action 3", Debug(())),
+        ],
+    )
+    "#;
+    let timing = BehaviorTiming::stepped(TEST_TIMESTEP).single_frame();
+    let trace = trace_behavior_for_frames(behavior, timing, 1);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Sequencer of nested actions",
+        "[2] STARTED Do action 0",
+        "[2] SUCCESS Do action 0",
+        "[3] STARTED Nested sequencer",
+        "[4] STARTED Do action 1",
+        "[4] SUCCESS Do action 1",
+        "[5] STARTED Do action 2",
+        "[5] SUCCESS Do action 2",
+        "[3] SUCCESS Nested sequencer",
+        "[6] STARTED Do action 3",
+        "[6] SUCCESS Do action 3",
+        "[1] SUCCESS Sequencer of nested actions",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+    // Same trace as one hop per frame
+    assert_eq!(&trace_behavior(behavior), &expected_trace);
+}
+
+#[test]
+fn tick_fixed_max_ticks_per_frame() {
+    let timing = BehaviorTiming::fixed(0.1).with_max_ticks_per_frame(3);
+    // The long frame drops its extra ticks, instead of catching up later
+    assert_eq!(ticks_per_frame(timing, &[0.25, 1.0, 0.1]), vec![2, 3, 1]);
+}
+
+#[test]
+fn tick_fixed_invalid_timestep() {
+    assert_eq!(
+        ticks_per_frame(BehaviorTiming::fixed(0.0), &[0.1, 0.1]),
+        vec![0, 0]
+    );
+    assert_eq!(
+        ticks_per_frame(BehaviorTiming::fixed(-1.0), &[0.1]),
+        vec![0]
+    );
+}
+
+#[test]
+fn tick_single_frame_never_blocks() {
+    let behavior = r#"
+    (
+        "Do forever",
+        Repeater((repeat: Forever)),
+        [
+            ("Do an action", Debug(())),
+        ]
+    )
+    "#;
+    // The tree never blocks, each frame stops after its hand-offs
+    let timing = BehaviorTiming::stepped(TEST_TIMESTEP)
+        .single_frame()
+        .with_max_handoffs(4);
+    let trace = trace_behavior_for_frames(behavior, timing, 2);
+    println!("{:#?}", trace);
+    assert_eq!(trace.len(), 8);
+}
+
+#[derive(Default, Resource)]
+struct PostTicks(u64);
+
+fn count_post_ticks(mut post_ticks: ResMut<PostTicks>) {
+    post_ticks.0 += 1;
+}
+
+#[test]
+fn tick_post_tick_schedule() {
+    let behavior = r#"
+    (
+        "Do forever",
+        Repeater((repeat: Forever)),
+        [
+            ("Do an action", Debug(())),
+        ]
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.insert_resource(Time::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::fixed(0.1).single_frame())
+        .init_resource::<PostTicks>()
+        .add_system(count_post_ticks.in_schedule(BehaviorPostTickSchedule));
+    spawn_test_tree(&mut app, &behavior, default());
+
+    // Runs once per tick, not once per hand-off pass or per frame
+    let mut now = Instant::now();
+    app.world.resource_mut::<Time>().update_with_instant(now);
+    for frame in [0.25, 0.12] {
+        now += Duration::from_secs_f64(frame);
+        app.world.resource_mut::<Time>().update_with_instant(now);
+        app.update();
+    }
+    let ticks = app.world.resource::<BehaviorClock>().ticks();
+    assert_eq!(ticks, 3);
+    assert_eq!(app.world.resource::<PostTicks>().0, ticks);
+}
+
+#[test]
+fn tick_fixed_signals_wait_for_tick() {
+    let behavior = r#"
+    (
+        "Wait for door",
+        WaitForEvent((signal: (prop: Value("door opened")))),
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.insert_resource(Time::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::fixed(0.1));
+    spawn_test_tree(&mut app, &behavior, default());
+
+    let mut now = Instant::now();
+    app.world.resource_mut::<Time>().update_with_instant(now);
+    let mut run_frames = |app: &mut App, frames: &[f64]| {
+        for frame in frames {
+            now += Duration::from_secs_f64(*frame);
+            app.world.resource_mut::<Time>().update_with_instant(now);
+            app.update();
+        }
+    };
+
+    // Start waiting, then signal while trees don't tick for a few frames
+    run_frames(&mut app, &[0.1, 0.1]);
+    app.world.send_event(BehaviorSignal::global("door opened"));
+    run_frames(&mut app, &[0.03, 0.03, 0.03]);
+    assert_eq!(app.world.resource::<BehaviorClock>().ticks(), 2);
+    run_frames(&mut app, &[0.1]);
+
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Wait for door", "[1] SUCCESS Wait for door"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[derive(Default, Resource)]
+struct SeenStarted(Vec<String>);
+
+fn see_started(
+    nodes: Query<&Name, (With<BehaviorNode>, With<BehaviorStarted>)>,
+    mut seen: ResMut<SeenStarted>,
+) {
+    seen.0.extend(nodes.iter().map(|name| name.to_string()));
+}
+
+#[test]
+fn tick_frame_plain_systems() {
+    let behavior = r#"
+    (
+        "Sequencer of a few actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Do action 1", Debug(())),
+        ],
+    )
+    "#;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.init_resource::<SeenStarted>().add_system(see_started);
+    spawn_test_tree(&mut app, &behavior, default());
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+
+    // Systems added with a plain `add_system` see every started node, like
+    // before the behavior schedule
+    assert_eq!(
+        app.world.resource::<SeenStarted>().0,
+        vec!["Sequencer of a few actions", "Do action 0", "Do action 1",]
+    );
+}
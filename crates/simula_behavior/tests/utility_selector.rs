@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn trace_with_seed(behavior: &str, seed: u64) -> BehaviorTrace {
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.insert_resource(BehaviorTiming::stepped(TEST_TIMESTEP))
+        .insert_resource(BehaviorSeed(seed));
+    spawn_test_tree(&mut app, &behavior, default());
+    for _ in 0..MAX_ITERS {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+#[test]
+fn utility_selector_highest_score() {
+    let behavior = r#"
+    (
+        "Pick best action",
+        UtilitySelector(()),
+        [
+            ("Score action 0", Score((score: (prop: Value(1.0)))), [
+                ("Do action 0", Debug(())),
+            ]),
+            ("Score action 1", Score((score: (prop: Value(3.0)))), [
+                ("Do action 1", Debug(())),
+            ]),
+            ("Score action 2", Score((score: (prop: Eval(eval: "1.0 + 1.0")))), [
+                ("Do action 2", Debug(())),
+            ]),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Pick best action",
+        "[4] STARTED Score action 1",
+        "[5] STARTED Do action 1",
+        "[5] SUCCESS Do action 1",
+        "[4] SUCCESS Score action 1",
+        "[1] SUCCESS Pick best action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn utility_selector_no_score() {
+    let behavior = r#"
+    (
+        "Pick best action",
+        UtilitySelector(()),
+        [
+            ("Score action 0", Score((score: (prop: Value(0.0)))), [
+                ("Do action 0", Debug(())),
+            ]),
+            ("Do action 1", Debug(())),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Pick best action",
+        "[1] FAILURE Pick best action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+// Action A lowers its own score below the score of action B
+const REPEATED: &str = r#"
+(
+    blackboard: [
+        (key: "a", default: Float(2.0)),
+        (key: "b", default: Float(1.5)),
+    ],
+    root: ("Pick twice", Repeater((repeat: Times(2))), [
+        ("Pick best action", UtilitySelector((hysteresis: HYSTERESIS)), [
+            ("Score A", Score((score: (prop: Eval(eval: "blackboard.a")))), [
+                ("Do A", SetBlackboard((key: (prop: Value("a")), value: (prop: Value(Float(1.0)))))),
+            ]),
+            ("Score B", Score((score: (prop: Eval(eval: "blackboard.b")))), [
+                ("Do B", Debug(())),
+            ]),
+        ]),
+    ]),
+)
+"#;
+
+#[test]
+fn utility_selector_without_hysteresis() {
+    let trace = trace_behavior(&REPEATED.replace("HYSTERESIS", "0.0"));
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Pick twice",
+        "[2] STARTED Pick best action",
+        "[3] STARTED Score A",
+        "[4] STARTED Do A",
+        "[4] SUCCESS Do A",
+        "[3] SUCCESS Score A",
+        "[2] SUCCESS Pick best action",
+        "[1] STARTED Pick twice",
+        "[2] STARTED Pick best action",
+        "[5] STARTED Score B",
+        "[6] STARTED Do B",
+        "[6] SUCCESS Do B",
+        "[5] SUCCESS Score B",
+        "[2] SUCCESS Pick best action",
+        "[1] SUCCESS Pick twice",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn utility_selector_with_hysteresis() {
+    let trace = trace_behavior(&REPEATED.replace("HYSTERESIS", "1.0"));
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Pick twice",
+        "[2] STARTED Pick best action",
+        "[3] STARTED Score A",
+        "[4] STARTED Do A",
+        "[4] SUCCESS Do A",
+        "[3] SUCCESS Score A",
+        "[2] SUCCESS Pick best action",
+        "[1] STARTED Pick twice",
+        "[2] STARTED Pick best action",
+        "[3] STARTED Score A",
+        "[4] STARTED Do A",
+        "[4] SUCCESS Do A",
+        "[3] SUCCESS Score A",
+        "[2] SUCCESS Pick best action",
+        "[1] SUCCESS Pick twice",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn utility_selector_random_weighted() {
+    let behavior = r#"
+    (
+        "Pick random action",
+        UtilitySelector((random: true)),
+        [
+            ("Score action 0", Score((score: (prop: Value(0.0)))), [
+                ("Do action 0", Debug(())),
+            ]),
+            ("Score action 1", Score((score: (prop: Value(1.0)))), [
+                ("Do action 1", Debug(())),
+            ]),
+            ("Score action 2", Score((score: (prop: Value(-1.0)))), [
+                ("Do action 2", Debug(())),
+            ]),
+        ],
+    )
+    "#;
+
+    // Children with a score of zero or less are never picked
+    for seed in 0..16 {
+        let trace = trace_with_seed(behavior, seed);
+        let expected_trace = BehaviorTrace::from_list(&[
+            "[1] STARTED Pick random action",
+            "[4] STARTED Score action 1",
+            "[5] STARTED Do action 1",
+            "[5] SUCCESS Do action 1",
+            "[4] SUCCESS Score action 1",
+            "[1] SUCCESS Pick random action",
+        ]);
+        assert_eq!(trace, expected_trace);
+    }
+}
+
+#[test]
+fn utility_selector_random_seeded() {
+    let behavior = r#"
+    (
+        "Pick random action",
+        UtilitySelector((random: true)),
+        [
+            ("Score action 0", Score((score: (prop: Value(1.0)))), [
+                ("Do action 0", Debug(())),
+            ]),
+            ("Score action 1", Score((score: (prop: Value(1.0)))), [
+                ("Do action 1", Debug(())),
+            ]),
+            ("Score action 2", Score((score: (prop: Value(2.0)))), [
+                ("Do action 2", Debug(())),
+            ]),
+        ],
+    )
+    "#;
+
+    // Same seed, same choice
+    let mut picked = vec![];
+    for seed in 0..16 {
+        let trace = trace_with_seed(behavior, seed);
+        assert_eq!(trace, trace_with_seed(behavior, seed));
+        assert_eq!(trace.0.len(), 6);
+        picked.push(trace.0[1].clone());
+    }
+
+    // Other seeds, other choices
+    picked.sort();
+    picked.dedup();
+    assert!(picked.len() > 1);
+}
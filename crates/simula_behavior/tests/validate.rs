@@ -0,0 +1,118 @@
+use simula_behavior::{prelude::*, test::*};
+use std::path::Path;
+
+fn diagnostics(behavior: &str) -> Vec<(Severity, DiagnosticKind, Vec<usize>)> {
+    let document = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    validate(&document, Path::new("../../assets"))
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.kind, diagnostic.path))
+        .collect()
+}
+
+#[test]
+fn validate_valid_tree() {
+    let behavior = r#"
+    (
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action 0", Debug(())),
+            ("Guard", Guard((condition: (prop: Eval(eval: "1 < 2")))), [
+                ("Do action 1", Debug(())),
+            ]),
+            ("Load subtree", Subtree((asset: "bht/d/delay.bht.ron"))),
+        ],
+    )
+    "#;
+    assert_eq!(diagnostics(behavior), vec![]);
+}
+
+#[test]
+fn validate_child_counts() {
+    let behavior = r#"
+    (
+        "Sequencer of nodes",
+        Sequencer(()),
+        [
+            ("Empty selector", Selector(())),
+            ("Action with child", Debug(()), [
+                ("Do action 0", Debug(())),
+            ]),
+            ("Inverter of two", Inverter(()), [
+                ("Do action 1", Debug(())),
+                ("Do action 2", Debug(())),
+            ]),
+        ],
+    )
+    "#;
+    assert_eq!(
+        diagnostics(behavior),
+        vec![
+            (Severity::Warning, DiagnosticKind::ChildCount, vec![0]),
+            (Severity::Error, DiagnosticKind::ChildCount, vec![1]),
+            (Severity::Error, DiagnosticKind::ChildCount, vec![2]),
+        ]
+    );
+}
+
+#[test]
+fn validate_scripts() {
+    let behavior = r#"
+    (
+        "Guard with broken script",
+        Guard((condition: (prop: Eval(eval: "1 <")))),
+        [
+            (
+                "Do action",
+                Debug((
+                    message: (prop: Eval(eval: "\"done \" +")),
+                    duration: (prop: Eval(eval: "1.0")),
+                )),
+            ),
+        ],
+    )
+    "#;
+    assert_eq!(
+        diagnostics(behavior),
+        vec![
+            (Severity::Error, DiagnosticKind::Script, vec![]),
+            (Severity::Error, DiagnosticKind::Script, vec![0]),
+        ]
+    );
+}
+
+#[test]
+fn validate_missing_subtree() {
+    let behavior = r#"
+    (
+        "Sequencer of subtrees",
+        Sequencer(()),
+        [
+            ("Load subtree", Subtree((asset: "bht/d/delay.bht.ron"))),
+            ("Load missing subtree", Subtree((asset: "bht/d/missing.bht.ron"))),
+        ],
+    )
+    "#;
+    assert_eq!(
+        diagnostics(behavior),
+        vec![(Severity::Error, DiagnosticKind::MissingSubtree, vec![1])]
+    );
+}
+
+#[test]
+fn validate_duplicate_names() {
+    let behavior = r#"
+    (
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action", Debug(())),
+            ("Do action", Debug(())),
+        ],
+    )
+    "#;
+    assert_eq!(
+        diagnostics(behavior),
+        vec![(Severity::Warning, DiagnosticKind::DuplicateName, vec![1])]
+    );
+}
@@ -0,0 +1,67 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn wait_for_event_signaled() {
+    let behavior = r#"
+    (
+        "Wait or open",
+        Any(()),
+        [
+            ("Wait for door", WaitForEvent((signal: (prop: Value("door opened"))))),
+            (
+                "Open door",
+                Sequencer(()),
+                [
+                    ("Open it", EmitSignal((signal: (prop: Value("door opened"))))),
+                    ("Stay open", Wait((duration: (prop: Value(1000.0))))),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Wait or open",
+        "[2] STARTED Wait for door",
+        "[3] STARTED Open door",
+        "[4] STARTED Open it",
+        "[4] SUCCESS Open it",
+        "[2] SUCCESS Wait for door",
+        "[5] STARTED Stay open",
+        "[1] SUCCESS Wait or open",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn wait_for_event_other_signal() {
+    let behavior = r#"
+    (
+        "Wait or open",
+        Any(()),
+        [
+            ("Wait for door", WaitForEvent((signal: (prop: Value("door opened"))))),
+            (
+                "Lose target",
+                Sequencer(()),
+                [
+                    ("Lose it", EmitSignal((signal: (prop: Value("target lost")), scope: Global))),
+                    ("Stay lost", Wait((duration: (prop: Value(1000.0))))),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Wait or open",
+        "[2] STARTED Wait for door",
+        "[3] STARTED Lose target",
+        "[4] STARTED Lose it",
+        "[4] SUCCESS Lose it",
+        "[5] STARTED Stay lost",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -23,6 +23,17 @@ impl Prng {
     pub fn rand_float_range(&mut self, a: f32, b: f32) -> f32 {
         self.rng.rand_float() * (b - a) + a
     }
+
+    /// Internal state, to continue the same sequence with `from_state`
+    pub fn state(&self) -> (u64, u64) {
+        self.rng.state()
+    }
+
+    pub fn from_state(state: (u64, u64)) -> Self {
+        Prng {
+            rng: Rand32::from_state(state),
+        }
+    }
 }
 
 impl Default for Prng {
@@ -0,0 +1,235 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+use clap::{Parser, ValueEnum};
+use scripting_behaviors::{
+    derived_behavior::{DerivedBehavior, DerivedBehaviorPlugin},
+    implemented_behavior::{ImplementedBehavior, ImplementedBehaviorPlugin},
+};
+use serde::{Deserialize, Serialize};
+use simula_behavior::{prelude::*, BehaviorTrace};
+use simula_script::ScriptContext;
+use std::path::{Path, PathBuf};
+
+/// Run or check behavior trees without a window. Exits with 1 if a file fails
+/// to parse or validate, with 2 if a tree fails or is still running after the
+/// maximum ticks.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Behavior files, or folders to search for `.bht.ron` files
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Behavior type the files are made of
+    #[arg(long, value_enum, default_value_t = BehaviorKind::Derived)]
+    behavior: BehaviorKind,
+    /// Only parse and validate, don't run
+    #[arg(long)]
+    check: bool,
+    /// Maximum number of ticks to run
+    #[arg(long, default_value_t = 1000)]
+    ticks: usize,
+    /// Seconds the behavior clock advances on every tick
+    #[arg(long, default_value_t = 1.0 / 60.0)]
+    step: f64,
+    /// Asset folder, subtrees are loaded from here. Defaults to `assets` in the
+    /// current folder if there is one, or to the assets of this repository.
+    #[arg(long)]
+    assets: Option<PathBuf>,
+}
+
+impl Args {
+    fn asset_folder(&self) -> PathBuf {
+        if let Some(assets) = &self.assets {
+            return assets.clone();
+        }
+        let assets = PathBuf::from("assets");
+        if assets.is_dir() {
+            assets
+        } else {
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum BehaviorKind {
+    Derived,
+    Implemented,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut files = vec![];
+    for path in &args.paths {
+        collect_files(path, &mut files);
+    }
+
+    let mut errors = 0;
+    let mut unsucceeded = 0;
+    for file in &files {
+        println!("{}", file.display());
+        let result = match args.behavior {
+            BehaviorKind::Derived => process::<DerivedBehavior>(file, &args),
+            BehaviorKind::Implemented => process::<ImplementedBehavior>(file, &args),
+        };
+        match result {
+            Ok(true) => {}
+            Ok(false) => unsucceeded += 1,
+            Err(err) => {
+                println!("  error: {}", err);
+                errors += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} files, {} with errors, {} failed or still running",
+        files.len(),
+        errors,
+        unsucceeded
+    );
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    if unsucceeded > 0 {
+        std::process::exit(2);
+    }
+}
+
+/// Collect behavior files, searching folders recursively
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let mut entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            println!("{}: {}", path.display(), err);
+            return;
+        }
+    };
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() || entry.to_string_lossy().ends_with(".bht.ron") {
+            collect_files(&entry, files);
+        }
+    }
+}
+
+/// Parse and validate a behavior file, then run it unless only checking.
+/// Returns if the root succeeded, always true when only checking.
+fn process<T>(file: &Path, args: &Args) -> Result<bool, String>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    let document = std::fs::read_to_string(file).map_err(|err| err.to_string())?;
+    let behavior_file =
+        BehaviorFile::<T>::from_document(&document).map_err(|err| err.to_string())?;
+
+    let diagnostics = validate(&behavior_file.root, &args.asset_folder());
+    for diagnostic in &diagnostics {
+        println!("  {}", diagnostic);
+    }
+    let validation_errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_error())
+        .count();
+    if validation_errors > 0 {
+        return Err(format!("{} validation errors", validation_errors));
+    }
+    if args.check {
+        println!("  ok");
+        return Ok(true);
+    }
+
+    let (trace, blackboard, status) = run(behavior_file, args);
+    println!("  trace:");
+    for entry in trace.iter() {
+        println!("    {}", entry);
+    }
+    println!("  blackboard:");
+    for (key, value) in blackboard.0.iter() {
+        println!("    {} = {:?}", key, value);
+    }
+    println!("  status: {}", status);
+    Ok(status == "SUCCESS")
+}
+
+/// Run a behavior tree until the root completes, or for the maximum ticks
+fn run<T>(
+    behavior_file: BehaviorFile<T>,
+    args: &Args,
+) -> (BehaviorTrace, BlackboardSnapshot, &'static str)
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    let asset_folder = args.asset_folder();
+    let asset_folder = std::fs::canonicalize(&asset_folder).unwrap_or(asset_folder);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin {
+            asset_folder: asset_folder.to_string_lossy().to_string(),
+            watch_for_changes: false,
+        })
+        .add_plugin(BehaviorPlugin)
+        .add_plugin(DerivedBehaviorPlugin)
+        .add_plugin(ImplementedBehaviorPlugin)
+        .insert_resource(BehaviorTiming::stepped(args.step))
+        .init_resource::<BehaviorTrace>();
+
+    // Spawn the tree, it is built and started when reset
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<T>>>()
+        .add(BehaviorAsset {
+            behavior: behavior_file.root,
+            blackboard: behavior_file.blackboard,
+            file_name: None,
+        });
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(BehaviorTree::<T>::create_script_context());
+    let tree = app
+        .world
+        .spawn((
+            Name::new("BHT: runner"),
+            behavior_handle,
+            script_ctx_handle,
+            BehaviorTree::<T>::default(),
+            BehaviorTreeReset::<T>::default(),
+        ))
+        .id();
+
+    let mut status = "RUNNING";
+    for _ in 0..args.ticks {
+        app.update();
+        let root = app
+            .world
+            .get::<Children>(tree)
+            .and_then(|children| children.first().copied());
+        if let Some(root) = root {
+            if app.world.get::<BehaviorSuccess>(root).is_some() {
+                status = "SUCCESS";
+                break;
+            }
+            if app.world.get::<BehaviorFailure>(root).is_some() {
+                status = "FAILURE";
+                break;
+            }
+        }
+    }
+
+    let mut state: SystemState<Blackboards> = SystemState::new(&mut app.world);
+    let blackboard = state
+        .get_mut(&mut app.world)
+        .snapshot(tree)
+        .unwrap_or_default();
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    (trace, blackboard, status)
+}
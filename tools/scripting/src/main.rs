@@ -13,11 +13,10 @@ use simula_viz::{
     lines::LinesPlugin,
 };
 
-use derived_behavior::{DerivedBehavior, DerivedBehaviorPlugin};
-use implemented_behavior::{ImplementedBehavior, ImplementedBehaviorPlugin};
-
-mod derived_behavior;
-mod implemented_behavior;
+use scripting_behaviors::{
+    derived_behavior::{DerivedBehavior, DerivedBehaviorPlugin},
+    implemented_behavior::{ImplementedBehavior, ImplementedBehaviorPlugin},
+};
 
 fn main() {
     App::new()
@@ -127,6 +126,9 @@ fn behavior_setup<T: BehaviorFactory>(
                     file_name: file_name.clone(),
                     entity: EntityTracker::None,
                     asset: AssetTracker::None,
+                    telemetry: TelemetryTracker::default(),
+                    breakpoints: vec![],
+                    recorder: false,
                 },
             );
             behavior_server
@@ -9,7 +9,7 @@ pub struct DerivedBehaviorPlugin;
 impl Plugin for DerivedBehaviorPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(BehaviorTreePlugin::<DerivedBehavior>::default())
-            .add_system(subtree::run::<DerivedBehavior>) // Subtrees are typed, need to register them separately
+            .add_behavior_system(subtree::run::<DerivedBehavior>) // Subtrees are typed, need to register them separately
             .register_type::<Subtree<DerivedBehavior>>();
     }
 }
@@ -26,15 +26,29 @@ pub enum DerivedBehavior {
     Debug(Debug),
     Selector(Selector),
     Sequencer(Sequencer),
+    ReactiveSelector(ReactiveSelector),
+    ReactiveSequencer(ReactiveSequencer),
+    UtilitySelector(UtilitySelector),
+    RandomSelector(RandomSelector),
+    ShuffledSequencer(ShuffledSequencer),
     All(All),
     Any(Any),
+    Parallel(Parallel),
     Repeater(Repeater),
     Inverter(Inverter),
     Succeeder(Succeeder),
     Wait(Wait),
+    SetBlackboard(SetBlackboard),
+    IncrementBlackboard(IncrementBlackboard),
+    CompareBlackboard(CompareBlackboard),
+    WaitForEvent(WaitForEvent),
+    EmitSignal(EmitSignal),
     Delay(Delay),
     Guard(Guard),
     Timeout(Timeout),
+    Score(Score),
+    Retry(Retry),
+    Cooldown(Cooldown),
     // Substrees are typed, can load same or different types of subtrees
     Subtree(Subtree<DerivedBehavior>),
     SubImpl(Subtree<ImplementedBehavior>),
@@ -62,15 +76,29 @@ impl BehaviorInspectable for DerivedBehavior {
             DerivedBehavior::Debug(_) => Color::hex("#235").unwrap(),
             DerivedBehavior::Selector(_) => Color::hex("#522").unwrap(),
             DerivedBehavior::Sequencer(_) => Color::hex("#252").unwrap(),
+            DerivedBehavior::ReactiveSelector(_) => Color::hex("#522").unwrap(),
+            DerivedBehavior::ReactiveSequencer(_) => Color::hex("#252").unwrap(),
+            DerivedBehavior::UtilitySelector(_) => Color::hex("#522").unwrap(),
+            DerivedBehavior::RandomSelector(_) => Color::hex("#522").unwrap(),
+            DerivedBehavior::ShuffledSequencer(_) => Color::hex("#252").unwrap(),
             DerivedBehavior::All(_) => Color::hex("#252").unwrap(),
             DerivedBehavior::Any(_) => Color::hex("#522").unwrap(),
+            DerivedBehavior::Parallel(_) => Color::hex("#252").unwrap(),
             DerivedBehavior::Repeater(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Inverter(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Succeeder(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Wait(_) => Color::hex("#235").unwrap(),
+            DerivedBehavior::SetBlackboard(_) => Color::hex("#235").unwrap(),
+            DerivedBehavior::IncrementBlackboard(_) => Color::hex("#235").unwrap(),
+            DerivedBehavior::CompareBlackboard(_) => Color::hex("#235").unwrap(),
+            DerivedBehavior::WaitForEvent(_) => Color::hex("#235").unwrap(),
+            DerivedBehavior::EmitSignal(_) => Color::hex("#235").unwrap(),
             DerivedBehavior::Delay(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Guard(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Timeout(_) => Color::hex("#440").unwrap(),
+            DerivedBehavior::Score(_) => Color::hex("#440").unwrap(),
+            DerivedBehavior::Retry(_) => Color::hex("#440").unwrap(),
+            DerivedBehavior::Cooldown(_) => Color::hex("#440").unwrap(),
             DerivedBehavior::Subtree(_) => Color::hex("#530").unwrap(),
             DerivedBehavior::SubImpl(_) => Color::hex("#530").unwrap(),
         }
@@ -82,15 +110,29 @@ impl BehaviorInspectable for DerivedBehavior {
             DerivedBehavior::Debug(_) => vec![<Debug as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Selector(_) => vec![<Selector as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Sequencer(_) => vec![<Sequencer as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::ReactiveSelector(_) => vec![<ReactiveSelector as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::ReactiveSequencer(_) => vec![<ReactiveSequencer as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::UtilitySelector(_) => vec![<UtilitySelector as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::RandomSelector(_) => vec![<RandomSelector as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::ShuffledSequencer(_) => vec![<ShuffledSequencer as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::All(_) => vec![<All as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Any(_) => vec![<Any as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Parallel(_) => vec![<Parallel as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Repeater(_) => vec![<Repeater as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Inverter(_) => vec![<Inverter as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Succeeder(_) => vec![<Succeeder as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Wait(_) => vec![<Wait as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::SetBlackboard(_) => vec![<SetBlackboard as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::IncrementBlackboard(_) => vec![<IncrementBlackboard as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::CompareBlackboard(_) => vec![<CompareBlackboard as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::WaitForEvent(_) => vec![<WaitForEvent as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::EmitSignal(_) => vec![<EmitSignal as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Delay(_) => vec![<Delay as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Guard(_) => vec![<Guard as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Timeout(_) => vec![<Timeout as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Score(_) => vec![<Score as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Retry(_) => vec![<Retry as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Cooldown(_) => vec![<Cooldown as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Subtree(_) => vec![<Subtree<DerivedBehavior> as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::SubImpl(_) => vec![<Subtree<ImplementedBehavior> as BehaviorSpec>::TYPE.as_ref()],
         }
@@ -1,5 +1,5 @@
 use bevy::{
-    ecs::system::EntityCommands,
+    ecs::{entity::EntityMap, system::EntityCommands},
     prelude::*,
     reflect::{TypeRegistry, TypeUuid},
 };
@@ -13,7 +13,7 @@ pub struct ImplementedBehaviorPlugin;
 impl Plugin for ImplementedBehaviorPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(BehaviorTreePlugin::<ImplementedBehavior>::default())
-            .add_system(subtree::run::<ImplementedBehavior>) // Subtrees are typed, need to register them separately
+            .add_behavior_system(subtree::run::<ImplementedBehavior>) // Subtrees are typed, need to register them separately
             .register_type::<Subtree<ImplementedBehavior>>();
     }
 }
@@ -178,6 +178,25 @@ impl BehaviorFactory for ImplementedBehavior {
         }
     }
 
+    fn scripts(&self) -> Vec<&str> {
+        match self {
+            ImplementedBehavior::Debug(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Selector(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Sequencer(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::All(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Any(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Repeater(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Inverter(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Succeeder(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Wait(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Delay(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Guard(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Timeout(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::Subtree(data) => BehaviorSpec::scripts(data),
+            ImplementedBehavior::AnotherTree(data) => BehaviorSpec::scripts(data),
+        }
+    }
+
     fn inner_reflect(&self) -> &dyn Reflect {
         match self {
             ImplementedBehavior::Debug(data) => data,
@@ -291,6 +310,51 @@ impl BehaviorFactory for ImplementedBehavior {
         Ok(())
     }
 
+    #[rustfmt::skip]
+    fn save_state(&self, clock: &BehaviorClock) -> Option<BehaviorNodeState> {
+        match self {
+            ImplementedBehavior::Debug(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Selector(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Sequencer(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::All(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Any(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Repeater(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Inverter(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Succeeder(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Wait(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Delay(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Guard(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Timeout(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::Subtree(data) => BehaviorSpec::save_state(data, clock),
+            ImplementedBehavior::AnotherTree(data) => BehaviorSpec::save_state(data, clock),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn restore_state(
+        &mut self,
+        state: &BehaviorNodeState,
+        clock: &BehaviorClock,
+        entity_map: &EntityMap,
+    ) {
+        match self {
+            ImplementedBehavior::Debug(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Selector(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Sequencer(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::All(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Any(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Repeater(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Inverter(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Succeeder(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Wait(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Delay(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Guard(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Timeout(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::Subtree(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+            ImplementedBehavior::AnotherTree(data) => BehaviorSpec::restore_state(data, state, clock, entity_map),
+        }
+    }
+
     fn typ(&self) -> BehaviorType {
         match self {
             ImplementedBehavior::Debug(_) => <Debug as BehaviorSpec>::TYPE,
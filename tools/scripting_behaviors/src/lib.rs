@@ -0,0 +1,2 @@
+pub mod derived_behavior;
+pub mod implemented_behavior;
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use scripting_behaviors::{
+    derived_behavior::{DerivedBehavior, DerivedBehaviorPlugin},
+    implemented_behavior::{ImplementedBehavior, ImplementedBehaviorPlugin},
+};
+use simula_behavior::{asset::subtree_asset, prelude::*, test::*};
+use std::path::Path;
+
+const ASSET_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/bht/d");
+
+fn has_subtree<T: BehaviorFactory>(behavior: &Behavior<T>) -> bool {
+    subtree_asset(behavior.data()).is_some() || behavior.nodes().iter().any(has_subtree)
+}
+
+fn trace_asset<T>(document: &str, plugin: impl Plugin) -> Vec<String>
+where
+    T: BehaviorFactory + for<'de> serde::Deserialize<'de>,
+{
+    let timing = BehaviorTiming::stepped(TEST_TIMESTEP);
+    let trace = trace_behavior_of::<T>(document, timing, MAX_ITERS, |app| {
+        app.add_asset::<BehaviorDocument>().add_plugin(plugin);
+    });
+    trace.0
+}
+
+// Trees without subtrees run the same with both behavior types, subtree
+// assets are loaded by the asset server and are not run here
+#[test]
+fn asset_traces() {
+    let mut files = std::fs::read_dir(Path::new(ASSET_DIR))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".bht.ron"))
+        .collect::<Vec<_>>();
+    files.sort();
+    assert!(!files.is_empty());
+
+    for file in files {
+        let document = std::fs::read_to_string(&file).unwrap();
+        let root = BehaviorFile::<DerivedBehavior>::from_document(&document)
+            .unwrap_or_else(|err| panic!("{}: {}", file.display(), err))
+            .root;
+        if has_subtree(&root) {
+            continue;
+        }
+
+        let derived = trace_asset::<DerivedBehavior>(&document, DerivedBehaviorPlugin);
+        let implemented = trace_asset::<ImplementedBehavior>(&document, ImplementedBehaviorPlugin);
+        assert_eq!(
+            derived.first(),
+            Some(&format!("[1] STARTED {}", root.name())),
+            "{}",
+            file.display()
+        );
+        assert_eq!(derived, implemented, "{}", file.display());
+    }
+}